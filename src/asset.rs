@@ -1,9 +1,18 @@
 pub mod atlas;
 pub mod blockstate;
+pub mod font;
+pub mod language;
+pub mod merge;
 pub mod model;
+pub mod recipe;
 pub mod selector;
+pub mod sound;
 pub mod texture;
 
 pub trait LoadableAsset<T = Self> {
     fn load_asset<R: AsRef<str>>(raw: R) -> anyhow::Result<T>;
 }
+
+pub trait MergeableAsset {
+    fn merge(&mut self, other: Self);
+}
@@ -0,0 +1,269 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::{bail, Context};
+use image::{imageops, RgbaImage};
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{
+    compile::vanilla::{load_texture, VanillaAssets},
+    minecraft::asset::{
+        font::{Font, FontProvider, FontProviderType},
+        types::identifier::Identifier,
+    },
+};
+
+use super::LoadableAsset;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum FontGeneric {
+    Preprocessed(FontPreprocessed),
+    Normal(Font),
+}
+
+impl LoadableAsset for FontGeneric {
+    fn load_asset<R: AsRef<str>>(raw: R) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(raw.as_ref())?)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct FontPreprocessed {
+    #[serde(default)]
+    providers: Vec<FontProvider>,
+    glyph_sheets: Vec<GlyphSheet>,
+}
+
+impl FontPreprocessed {
+    /// Builds every glyph sheet, returning the font and the sheet textures it references.
+    ///
+    /// Glyph textures that aren't in the pack are read from the vanilla assets.
+    pub fn compile(
+        self,
+        textures: &HashMap<Identifier, PathBuf>,
+        vanilla: Option<&VanillaAssets>,
+    ) -> anyhow::Result<(Font, Vec<(Identifier, RgbaImage)>)> {
+        let mut providers = self.providers;
+        let mut sheets = Vec::with_capacity(self.glyph_sheets.len());
+
+        for glyph_sheet in self.glyph_sheets {
+            let (provider, sheet) = glyph_sheet.build(textures, vanilla)?;
+            providers.push(provider.into());
+            sheets.push(sheet);
+        }
+
+        Ok((Font { providers }, sheets))
+    }
+
+    /// Every glyph texture the glyph sheets are built from.
+    pub fn glyph_textures(&self) -> impl Iterator<Item = Identifier> + '_ {
+        self.glyph_sheets.iter().flat_map(|glyph_sheet| {
+            glyph_sheet
+                .chars
+                .values()
+                .map(|glyph| glyph_sheet.glyph_id(glyph))
+        })
+    }
+}
+
+/// A bitmap provider assembled from a folder of single glyph textures.
+#[derive(Debug, Deserialize)]
+pub struct GlyphSheet {
+    /// Where the sheet is written; the same format as a bitmap provider's `file`.
+    file: Identifier,
+    /// The texture folder holding one texture per glyph.
+    glyphs: Identifier,
+    /// Maps each character to the name of its glyph texture.
+    chars: IndexMap<char, String>,
+    height: Option<i32>,
+    ascent: i32,
+    #[serde(default = "GlyphSheet::default_columns")]
+    columns: usize,
+}
+
+impl GlyphSheet {
+    const EMPTY_GLYPH: char = '\0';
+
+    #[inline]
+    fn default_columns() -> usize {
+        16
+    }
+
+    fn build(
+        self,
+        textures: &HashMap<Identifier, PathBuf>,
+        vanilla: Option<&VanillaAssets>,
+    ) -> anyhow::Result<(FontProviderType, (Identifier, RgbaImage))> {
+        if self.chars.is_empty() {
+            bail!("Glyph sheet has no glyphs: {}", self.file);
+        }
+
+        let glyph_images = self
+            .chars
+            .values()
+            .map(|glyph| self.load_glyph(glyph, textures, vanilla))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        let (glyph_width, glyph_height) = glyph_images[0].dimensions();
+
+        for (glyph, image) in self.chars.values().zip(&glyph_images) {
+            if image.dimensions() != (glyph_width, glyph_height) {
+                bail!(
+                    "Glyph '{}' is {}x{}; expected {}x{}: {}",
+                    glyph,
+                    image.width(),
+                    image.height(),
+                    glyph_width,
+                    glyph_height,
+                    self.glyphs
+                );
+            }
+        }
+
+        let columns = self.columns.max(1);
+        let rows = self.chars.len().div_ceil(columns);
+        let mut sheet = RgbaImage::new(glyph_width * columns as u32, glyph_height * rows as u32);
+        let mut chars = vec![String::new(); rows];
+
+        for (index, (character, image)) in self.chars.keys().zip(&glyph_images).enumerate() {
+            let (row, column) = (index / columns, index % columns);
+
+            imageops::replace(
+                &mut sheet,
+                image,
+                (column as u32 * glyph_width).into(),
+                (row as u32 * glyph_height).into(),
+            );
+            chars[row].push(*character);
+        }
+
+        // Every row must span the full width of the sheet.
+        if let Some(last_row) = chars.last_mut() {
+            let padding = columns - last_row.chars().count();
+            last_row.extend(std::iter::repeat_n(Self::EMPTY_GLYPH, padding));
+        }
+
+        let texture_id = Identifier::new(&self.file.namespace, self.file.path.with_extension(""));
+
+        Ok((
+            FontProviderType::Bitmap {
+                file: self.file,
+                height: self.height,
+                ascent: self.ascent,
+                chars,
+            },
+            (texture_id, sheet),
+        ))
+    }
+
+    fn glyph_id(&self, glyph: &str) -> Identifier {
+        Identifier::new(&self.glyphs.namespace, self.glyphs.path.join(glyph))
+    }
+
+    fn load_glyph(
+        &self,
+        glyph: &str,
+        textures: &HashMap<Identifier, PathBuf>,
+        vanilla: Option<&VanillaAssets>,
+    ) -> anyhow::Result<RgbaImage> {
+        load_texture(&self.glyph_id(glyph), textures, vanilla)
+            .with_context(|| format!("Failed to load glyph: {}", glyph))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{ImageFormat, Rgba};
+
+    use super::*;
+
+    #[test]
+    fn build_glyph_sheet() {
+        let directory = tempfile::tempdir().unwrap();
+        let mut textures = HashMap::new();
+
+        for (name, color) in [("heart", [255, 0, 0, 255]), ("star", [255, 255, 0, 255])] {
+            let path = directory.path().join(name).with_extension("png");
+            RgbaImage::from_pixel(2, 3, Rgba(color))
+                .save(&path)
+                .unwrap();
+            textures.insert(
+                Identifier::new("quark", format!("font/icons/{}", name)),
+                path,
+            );
+        }
+
+        let glyph_sheet = GlyphSheet {
+            file: Identifier::new("quark", "font/icons.png"),
+            glyphs: Identifier::new("quark", "font/icons"),
+            chars: IndexMap::from([
+                ('\u{e000}', "heart".to_string()),
+                ('\u{e001}', "star".to_string()),
+            ]),
+            height: None,
+            ascent: 7,
+            columns: 3,
+        };
+
+        let (provider, (texture_id, sheet)) = glyph_sheet.build(&textures, None).unwrap();
+
+        assert_eq!(texture_id, Identifier::new("quark", "font/icons"));
+        assert_eq!(sheet.dimensions(), (6, 3));
+        assert_eq!(sheet.get_pixel(2, 0), &Rgba([255, 255, 0, 255]));
+        assert_eq!(sheet.get_pixel(4, 0), &Rgba([0, 0, 0, 0]));
+        assert_eq!(
+            provider,
+            FontProviderType::Bitmap {
+                file: Identifier::new("quark", "font/icons.png"),
+                height: None,
+                ascent: 7,
+                chars: vec!["\u{e000}\u{e001}\u{0}".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn build_glyph_sheet_missing_glyph() {
+        let glyph_sheet = GlyphSheet {
+            file: Identifier::new("quark", "font/icons.png"),
+            glyphs: Identifier::new("quark", "font/icons"),
+            chars: IndexMap::from([('\u{e000}', "heart".to_string())]),
+            height: None,
+            ascent: 7,
+            columns: 16,
+        };
+
+        assert!(glyph_sheet.build(&HashMap::new(), None).is_err());
+    }
+
+    #[test]
+    fn build_glyph_sheet_vanilla_glyph() {
+        let mut raw = Cursor::new(Vec::new());
+        RgbaImage::from_pixel(2, 3, Rgba([255, 0, 0, 255]))
+            .write_to(&mut raw, ImageFormat::Png)
+            .unwrap();
+        let vanilla = VanillaAssets {
+            textures: HashMap::from([(
+                Identifier::minecraft("font/icons/heart"),
+                raw.into_inner(),
+            )]),
+            ..Default::default()
+        };
+
+        let glyph_sheet = GlyphSheet {
+            file: Identifier::new("quark", "font/icons.png"),
+            glyphs: Identifier::minecraft("font/icons"),
+            chars: IndexMap::from([('\u{e000}', "heart".to_string())]),
+            height: None,
+            ascent: 7,
+            columns: 16,
+        };
+
+        let (_, (_, sheet)) = glyph_sheet.build(&HashMap::new(), Some(&vanilla)).unwrap();
+
+        assert_eq!(sheet.get_pixel(0, 0), &Rgba([255, 0, 0, 255]));
+    }
+}
@@ -0,0 +1,188 @@
+use anyhow::Context;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+/// How a file is combined with the copies of the same asset from earlier bundles.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MergeStrategy {
+    /// The later file replaces the earlier one entirely.
+    #[default]
+    Override,
+    /// Objects are merged key by key and arrays are concatenated.
+    Append,
+    /// Objects are merged key by key; arrays are replaced and `null` removes a key.
+    DeepMerge,
+}
+
+impl MergeStrategy {
+    /// The top level key of the merge marker; kept apart from the `mcpacker` settings of models.
+    const MARKER_KEY: &'static str = "mcpacker_merge";
+
+    pub fn merge(self, base: &mut Value, other: Value) {
+        match self {
+            Self::Override => *base = other,
+            Self::Append => Self::append(base, other),
+            Self::DeepMerge => Self::deep_merge(base, other),
+        }
+    }
+
+    /// Removes the merge marker from an asset, returning the strategy it requested.
+    ///
+    /// ```json
+    /// { "mcpacker_merge": { "strategy": "append" } }
+    /// ```
+    pub fn take_marker(value: &mut Value) -> anyhow::Result<Option<Self>> {
+        let Some(marker) = value
+            .as_object_mut()
+            .and_then(|object| object.remove(Self::MARKER_KEY))
+        else {
+            return Ok(None);
+        };
+
+        #[derive(Deserialize)]
+        #[serde(deny_unknown_fields)]
+        struct Marker {
+            strategy: MergeStrategy,
+        }
+
+        let marker = serde_json::from_value::<Marker>(marker)
+            .with_context(|| format!("Failed to parse \"{}\" marker", Self::MARKER_KEY))?;
+
+        Ok(Some(marker.strategy))
+    }
+
+    fn append(base: &mut Value, other: Value) {
+        match (base, other) {
+            (Value::Object(base), Value::Object(other)) => {
+                Self::merge_objects(base, other, Self::append)
+            }
+            (Value::Array(base), Value::Array(mut other)) => base.append(&mut other),
+            (base, other) => *base = other,
+        }
+    }
+
+    fn deep_merge(base: &mut Value, other: Value) {
+        match (base, other) {
+            (Value::Object(base), Value::Object(other)) => {
+                for (key, value) in other {
+                    if value.is_null() {
+                        base.remove(&key);
+                    } else if let Some(existing) = base.get_mut(&key) {
+                        Self::deep_merge(existing, value);
+                    } else {
+                        base.insert(key, value);
+                    }
+                }
+            }
+            (base, other) => *base = other,
+        }
+    }
+
+    fn merge_objects(
+        base: &mut Map<String, Value>,
+        other: Map<String, Value>,
+        merge: fn(&mut Value, Value),
+    ) {
+        for (key, value) in other {
+            if let Some(existing) = base.get_mut(&key) {
+                merge(existing, value);
+            } else {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn append_atlas_sources() {
+        let mut base = json!({
+            "sources": [{ "type": "directory", "source": "block", "prefix": "block/" }]
+        });
+        MergeStrategy::Append.merge(
+            &mut base,
+            json!({ "sources": [{ "type": "single", "resource": "quark:glow" }] }),
+        );
+
+        assert_eq!(
+            base,
+            json!({
+                "sources": [
+                    { "type": "directory", "source": "block", "prefix": "block/" },
+                    { "type": "single", "resource": "quark:glow" }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn deep_merge_variants() {
+        let mut base = json!({
+            "variants": {
+                "facing=north": { "model": "block/furnace" },
+                "facing=south": { "model": "block/furnace", "y": 180 }
+            }
+        });
+        MergeStrategy::DeepMerge.merge(
+            &mut base,
+            json!({
+                "variants": {
+                    "facing=north": { "model": "quark:block/furnace" },
+                    "facing=south": { "y": null }
+                }
+            }),
+        );
+
+        assert_eq!(
+            base,
+            json!({
+                "variants": {
+                    "facing=north": { "model": "quark:block/furnace" },
+                    "facing=south": { "model": "block/furnace" }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn override_replaces() {
+        let mut base = json!({ "multipart": [{ "apply": { "model": "block/a" } }] });
+        let other = json!({ "variants": { "": { "model": "block/b" } } });
+        MergeStrategy::Override.merge(&mut base, other.clone());
+
+        assert_eq!(base, other);
+    }
+
+    #[test]
+    fn take_marker() {
+        let mut value = json!({
+            "mcpacker_merge": { "strategy": "append" },
+            "mcpacker": { "virtual": true },
+            "elements": []
+        });
+
+        assert_eq!(
+            MergeStrategy::take_marker(&mut value).unwrap(),
+            Some(MergeStrategy::Append)
+        );
+        assert_eq!(
+            value,
+            json!({ "mcpacker": { "virtual": true }, "elements": [] })
+        );
+        assert_eq!(MergeStrategy::take_marker(&mut value).unwrap(), None);
+    }
+
+    #[test]
+    fn marker_unknown_field() {
+        let mut value =
+            json!({ "mcpacker_merge": { "strategy": "append", "stratgy": "override" } });
+
+        assert!(MergeStrategy::take_marker(&mut value).is_err());
+    }
+}
@@ -4,15 +4,21 @@ use anyhow::{bail, Context};
 use indexmap::IndexMap;
 use serde::Deserialize;
 
-use crate::minecraft::asset::{
-    model::{flip::Flip, rotate::Rotatable, translate::Translate, Axis, CullDirection, Model},
-    types::{
-        identifier::{AssetType, Identifier},
-        rotation::StateRotation,
-        variable::VariableIdentifier,
-        vec::Vec3,
+use crate::{
+    compile::vanilla::VanillaAssets,
+    minecraft::asset::{
+        model::{
+            flip::Flip, rotate::Rotatable, scale::Scale, translate::Translate, Axis, CullDirection,
+            Model,
+        },
+        types::{
+            identifier::{AssetType, Identifier},
+            rotation::StateRotation,
+            variable::VariableIdentifier,
+            vec::Vec3,
+        },
+        Asset,
     },
-    Asset,
 };
 
 use super::LoadableAsset;
@@ -63,6 +69,9 @@ pub struct ModelPart {
     optional: bool,
     #[serde(default)]
     transformations: Vec<Transformation>,
+    /// Rotations keep textures aligned to the world instead of turning them with the part.
+    #[serde(default)]
+    uvlock: bool,
     cullface: Option<CullDirectionAuto>,
     #[serde(default)]
     textures: IndexMap<String, VariableIdentifier>,
@@ -120,6 +129,20 @@ enum Transformation {
         x: StateRotation,
         #[serde(default)]
         y: StateRotation,
+        #[serde(default)]
+        z: StateRotation,
+    },
+    /// Any angle around any origin; what isn't a quarter turn becomes the rotation of elements.
+    ///
+    /// Only the largest angle of any format is checked here; validation checks the remaining
+    /// angle against what the pack format accepts.
+    RotateFree {
+        axis: Axis,
+        angle: f32,
+        #[serde(default = "Transformation::default_origin")]
+        origin: Vec3,
+        #[serde(default)]
+        rescale: bool,
     },
     Translate {
         amount: Vec3,
@@ -127,6 +150,20 @@ enum Transformation {
     Flip {
         axis: Axis,
     },
+    Scale {
+        factor: Vec3,
+        #[serde(default = "Transformation::default_origin")]
+        pivot: Vec3,
+        /// Keeps the size of texels instead of stretching the texture with the faces.
+        #[serde(default)]
+        uv: bool,
+    },
+}
+
+impl Transformation {
+    fn default_origin() -> Vec3 {
+        Vec3::new(8.0, 8.0, 8.0)
+    }
 }
 
 impl ModelPreprocessed {
@@ -134,8 +171,10 @@ impl ModelPreprocessed {
         &self,
         raw_models: &HashMap<Identifier, Model>,
         prepocessed_models: &IndexMap<Identifier, ModelPreprocessed>,
+        vanilla: Option<&VanillaAssets>,
     ) -> anyhow::Result<Model> {
-        let mut builder = ModelBuilder::new(raw_models, prepocessed_models, self.import.clone());
+        let mut builder =
+            ModelBuilder::new(raw_models, prepocessed_models, vanilla, self.import.clone());
 
         builder.add_compositon(&self.composition)?;
 
@@ -146,6 +185,7 @@ impl ModelPreprocessed {
 struct ModelBuilder<'a> {
     raw_models: &'a HashMap<Identifier, Model>,
     prepocessed_models: &'a IndexMap<Identifier, ModelPreprocessed>,
+    vanilla: Option<&'a VanillaAssets>,
     output_model: Model,
     import_table: HashMap<String, ModelOrId>,
 }
@@ -154,11 +194,13 @@ impl<'a> ModelBuilder<'a> {
     fn new(
         raw_models: &'a HashMap<Identifier, Model>,
         prepocessed_models: &'a IndexMap<Identifier, ModelPreprocessed>,
+        vanilla: Option<&'a VanillaAssets>,
         import_table: HashMap<String, ModelOrId>,
     ) -> Self {
         Self {
             raw_models,
             prepocessed_models,
+            vanilla,
             import_table,
             output_model: Model::default(),
         }
@@ -210,14 +252,34 @@ impl<'a> ModelBuilder<'a> {
             lookup_model.parent.take();
         }
 
+        // Textures would otherwise follow the auto UV of wherever the faces end up.
+        if !part.transformations.is_empty() {
+            lookup_model.bake_uv();
+        }
+
         for transform in &part.transformations {
             match transform {
-                Transformation::Rotate { x, y } => {
+                Transformation::Rotate { x, y, z } if part.uvlock => {
+                    lookup_model.rotate_uvlock(x, y, z)
+                }
+                Transformation::Rotate { x, y, z } => {
                     lookup_model.rotate_x(x);
                     lookup_model.rotate_y(y);
+                    lookup_model.rotate_z(z);
                 }
+                Transformation::RotateFree {
+                    axis,
+                    angle,
+                    origin,
+                    rescale,
+                } => lookup_model
+                    .rotate_free(axis, *angle, origin, *rescale, part.uvlock)
+                    .with_context(|| format!("Failed to rotate model part: {}", part.model))?,
                 Transformation::Translate { amount } => lookup_model.translate(amount),
                 Transformation::Flip { axis } => lookup_model.flip(axis),
+                Transformation::Scale { factor, pivot, uv } => lookup_model
+                    .scale(factor, pivot, *uv)
+                    .with_context(|| format!("Failed to scale model part: {}", part.model))?,
             }
         }
 
@@ -268,6 +330,10 @@ impl<'a> ModelBuilder<'a> {
     fn lookup_model(&self, model_id: &Identifier) -> anyhow::Result<&'a Model> {
         self.raw_models
             .get(model_id)
+            .or_else(|| {
+                self.vanilla
+                    .and_then(|vanilla| vanilla.models.get(model_id))
+            })
             .with_context(|| format!("Failed to lookup model: {}", model_id))
     }
 
@@ -0,0 +1,487 @@
+use std::{collections::HashMap, fmt, path::PathBuf, str::FromStr};
+
+use anyhow::{anyhow, bail, Context};
+use image::{imageops, Rgba, RgbaImage};
+use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr, OneOrMany};
+
+use crate::{
+    compile::vanilla::{load_texture, VanillaAssets},
+    minecraft::asset::types::identifier::Identifier,
+};
+
+use super::LoadableAsset;
+
+pub type PaletteMapping = HashMap<[u8; 3], Rgba<u8>>;
+
+/// A texture generated at compile time from other textures.
+///
+/// The textures a recipe reads are still written to the pack, since models and atlas
+/// sources may use them as well.
+///
+/// ```toml
+/// base = "block/glass"
+///
+/// [[operations]]
+/// type = "tint"
+/// color = "#ff4040"
+/// ```
+#[derive(Debug, Deserialize)]
+pub struct TextureRecipe {
+    pub base: Identifier,
+    #[serde(default)]
+    pub operations: Vec<RecipeOperation>,
+}
+
+impl LoadableAsset for TextureRecipe {
+    fn load_asset<R: AsRef<str>>(raw: R) -> anyhow::Result<Self> {
+        Ok(toml::from_str(raw.as_ref())?)
+    }
+}
+
+#[serde_as]
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecipeOperation {
+    /// Multiplies every pixel by a color.
+    Tint {
+        #[serde_as(as = "DisplayFromStr")]
+        color: Color,
+    },
+    /// Composes each texture on top, in order.
+    Overlay {
+        #[serde_as(as = "OneOrMany<_>")]
+        textures: Vec<Identifier>,
+    },
+    /// Swaps each color found in the key for the color at the same position in the palette.
+    Palette {
+        key: Identifier,
+        palette: Identifier,
+    },
+    Crop {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    /// Moves the pixels without resizing; uncovered pixels become transparent.
+    Offset {
+        x: i64,
+        y: i64,
+    },
+    Flip {
+        axis: FlipAxis,
+    },
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FlipAxis {
+    Horizontal,
+    Vertical,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color([u8; 3]);
+
+impl FromStr for Color {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let hex = s
+            .strip_prefix('#')
+            .filter(|hex| hex.len() == 6)
+            .with_context(|| format!("Expected a color in the format #rrggbb: {}", s))?;
+        let value = u32::from_str_radix(hex, 16)
+            .map_err(|e| anyhow!("Failed to parse color '{}': {}", s, e))?;
+        let [_, r, g, b] = value.to_be_bytes();
+
+        Ok(Self([r, g, b]))
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [r, g, b] = self.0;
+        write!(f, "#{:02x}{:02x}{:02x}", r, g, b)
+    }
+}
+
+/// Builds every recipe; recipes may use the output of other recipes.
+pub fn resolve_recipes(
+    recipes: &HashMap<Identifier, TextureRecipe>,
+    textures: &HashMap<Identifier, PathBuf>,
+    vanilla: Option<&VanillaAssets>,
+) -> anyhow::Result<HashMap<Identifier, RgbaImage>> {
+    let mut resolver = RecipeResolver {
+        recipes,
+        textures,
+        vanilla,
+        resolved: HashMap::with_capacity(recipes.len()),
+        resolving: Vec::new(),
+    };
+
+    for recipe_id in recipes.keys() {
+        resolver
+            .resolve(recipe_id)
+            .with_context(|| format!("Failed to build texture recipe: {}", recipe_id))?;
+    }
+
+    Ok(resolver.resolved)
+}
+
+struct RecipeResolver<'a> {
+    recipes: &'a HashMap<Identifier, TextureRecipe>,
+    textures: &'a HashMap<Identifier, PathBuf>,
+    vanilla: Option<&'a VanillaAssets>,
+    resolved: HashMap<Identifier, RgbaImage>,
+    resolving: Vec<Identifier>,
+}
+
+impl RecipeResolver<'_> {
+    fn resolve(&mut self, id: &Identifier) -> anyhow::Result<RgbaImage> {
+        if let Some(texture) = self.resolved.get(id) {
+            return Ok(texture.clone());
+        }
+
+        let Some(recipe) = self.recipes.get(id) else {
+            return load_texture(id, self.textures, self.vanilla);
+        };
+
+        if self.resolving.contains(id) {
+            let cycle = self
+                .resolving
+                .iter()
+                .chain([id])
+                .map(Identifier::to_string)
+                .collect::<Vec<_>>();
+            bail!("Texture recipes form a cycle: {}", cycle.join(" -> "));
+        }
+
+        self.resolving.push(id.clone());
+
+        let mut texture = self.resolve(&recipe.base)?;
+
+        for operation in &recipe.operations {
+            texture = self.apply(operation, texture)?;
+        }
+
+        self.resolving.pop();
+        self.resolved.insert(id.clone(), texture.clone());
+
+        Ok(texture)
+    }
+
+    fn apply(
+        &mut self,
+        operation: &RecipeOperation,
+        mut texture: RgbaImage,
+    ) -> anyhow::Result<RgbaImage> {
+        match operation {
+            RecipeOperation::Tint { color } => {
+                tint(&mut texture, *color);
+                Ok(texture)
+            }
+            RecipeOperation::Overlay { textures } => {
+                for layer_id in textures {
+                    let layer = self.resolve(layer_id)?;
+
+                    if layer.dimensions() != texture.dimensions() {
+                        bail!(
+                            "Overlay is {}x{}; expected {}x{}: {}",
+                            layer.width(),
+                            layer.height(),
+                            texture.width(),
+                            texture.height(),
+                            layer_id
+                        );
+                    }
+
+                    imageops::overlay(&mut texture, &layer, 0, 0);
+                }
+
+                Ok(texture)
+            }
+            RecipeOperation::Palette { key, palette } => {
+                let mapping = palette_mapping(&self.resolve(key)?, &self.resolve(palette)?)
+                    .with_context(|| format!("Palette doesn't match its key: {}", palette))?;
+                Ok(permute(&texture, &mapping))
+            }
+            RecipeOperation::Crop {
+                x,
+                y,
+                width,
+                height,
+            } => {
+                let outside = |start: &u32, size: &u32, limit| {
+                    start.checked_add(*size).is_none_or(|end| end > limit)
+                };
+
+                if outside(x, width, texture.width()) || outside(y, height, texture.height()) {
+                    bail!(
+                        "Crop of {}x{} at {}, {} is outside of the {}x{} texture",
+                        width,
+                        height,
+                        x,
+                        y,
+                        texture.width(),
+                        texture.height()
+                    );
+                }
+
+                Ok(imageops::crop_imm(&texture, *x, *y, *width, *height).to_image())
+            }
+            RecipeOperation::Offset { x, y } => {
+                let mut offset = RgbaImage::new(texture.width(), texture.height());
+                imageops::replace(&mut offset, &texture, *x, *y);
+                Ok(offset)
+            }
+            RecipeOperation::Flip { axis } => Ok(match axis {
+                FlipAxis::Horizontal => imageops::flip_horizontal(&texture),
+                FlipAxis::Vertical => imageops::flip_vertical(&texture),
+            }),
+        }
+    }
+}
+
+fn tint(texture: &mut RgbaImage, Color(color): Color) {
+    for Rgba([r, g, b, _]) in texture.pixels_mut() {
+        for (channel, tint) in [r, g, b].into_iter().zip(color) {
+            *channel = (*channel as u16 * tint as u16 / u8::MAX as u16) as u8;
+        }
+    }
+}
+
+/// Pairs each pixel of the key with the pixel at the same position in the palette.
+pub fn palette_mapping(key: &RgbaImage, palette: &RgbaImage) -> anyhow::Result<PaletteMapping> {
+    if key.dimensions() != palette.dimensions() {
+        bail!(
+            "Palette is {}x{}; expected {}x{}",
+            palette.width(),
+            palette.height(),
+            key.width(),
+            key.height()
+        );
+    }
+
+    Ok(key
+        .pixels()
+        .zip(palette.pixels())
+        .map(|(Rgba([r, g, b, _]), color)| ([*r, *g, *b], *color))
+        .collect())
+}
+
+/// Swaps each color found in the key; the original alpha is multiplied with the palette's.
+pub fn permute(texture: &RgbaImage, mapping: &PaletteMapping) -> RgbaImage {
+    let mut permuted = texture.clone();
+
+    for pixel in permuted.pixels_mut() {
+        let Rgba([r, g, b, alpha]) = *pixel;
+
+        if alpha == 0 {
+            continue;
+        }
+
+        if let Some(Rgba([r, g, b, palette_alpha])) = mapping.get(&[r, g, b]) {
+            let alpha = (alpha as u16 * *palette_alpha as u16 / u8::MAX as u16) as u8;
+            *pixel = Rgba([*r, *g, *b, alpha]);
+        }
+    }
+
+    permuted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image(pixels: &[[u8; 4]]) -> RgbaImage {
+        RgbaImage::from_fn(pixels.len() as u32, 1, |x, _| Rgba(pixels[x as usize]))
+    }
+
+    fn recipe(raw: &str) -> TextureRecipe {
+        TextureRecipe::load_asset(raw).unwrap()
+    }
+
+    fn resolve_with(
+        recipes: HashMap<Identifier, TextureRecipe>,
+        textures: HashMap<Identifier, RgbaImage>,
+    ) -> anyhow::Result<HashMap<Identifier, RgbaImage>> {
+        let mut resolver = RecipeResolver {
+            recipes: &recipes,
+            textures: &HashMap::new(),
+            vanilla: None,
+            resolved: textures,
+            resolving: Vec::new(),
+        };
+
+        for recipe_id in recipes.keys() {
+            resolver.resolve(recipe_id)?;
+        }
+
+        Ok(resolver.resolved)
+    }
+
+    #[test]
+    fn parse_color() {
+        assert_eq!(Color::from_str("#ff4010").unwrap(), Color([255, 64, 16]));
+        assert_eq!(Color([255, 64, 16]).to_string(), "#ff4010");
+        assert!(Color::from_str("ff4010").is_err());
+    }
+
+    #[test]
+    fn permute_texture() {
+        let key = image(&[[10, 10, 10, 255], [20, 20, 20, 255]]);
+        let palette = image(&[[200, 0, 0, 255], [0, 200, 0, 128]]);
+        let mapping = palette_mapping(&key, &palette).unwrap();
+
+        let texture = image(&[
+            [10, 10, 10, 255],
+            [20, 20, 20, 255],
+            [30, 30, 30, 255],
+            [10, 10, 10, 0],
+        ]);
+
+        assert_eq!(
+            permute(&texture, &mapping),
+            image(&[
+                [200, 0, 0, 255],
+                [0, 200, 0, 128],
+                [30, 30, 30, 255],
+                [10, 10, 10, 0],
+            ])
+        );
+    }
+
+    #[test]
+    fn palette_size_mismatch() {
+        let key = image(&[[10, 10, 10, 255], [20, 20, 20, 255]]);
+        let palette = image(&[[200, 0, 0, 255]]);
+
+        assert!(palette_mapping(&key, &palette).is_err());
+    }
+
+    #[test]
+    fn chained_recipes() {
+        let recipes = HashMap::from([
+            (
+                Identifier::new("quark", "block/red_glass"),
+                recipe(
+                    r##"
+                    base = "quark:block/clear_glass"
+
+                    [[operations]]
+                    type = "tint"
+                    color = "#ff8000"
+                    "##,
+                ),
+            ),
+            (
+                Identifier::new("quark", "block/clear_glass"),
+                recipe(
+                    r#"
+                    base = "block/glass"
+
+                    [[operations]]
+                    type = "flip"
+                    axis = "horizontal"
+
+                    [[operations]]
+                    type = "overlay"
+                    textures = "block/glass_streaks"
+
+                    [[operations]]
+                    type = "offset"
+                    x = 1
+                    y = 0
+                    "#,
+                ),
+            ),
+        ]);
+
+        let textures = HashMap::from([
+            (
+                Identifier::minecraft("block/glass"),
+                image(&[[200, 200, 200, 255], [100, 100, 100, 255], [0, 0, 0, 0]]),
+            ),
+            (
+                Identifier::minecraft("block/glass_streaks"),
+                image(&[[0, 0, 0, 0], [0, 0, 0, 0], [255, 255, 255, 255]]),
+            ),
+        ]);
+
+        let resolved = resolve_with(recipes, textures).unwrap();
+
+        assert_eq!(
+            resolved[&Identifier::new("quark", "block/red_glass")],
+            image(&[[0, 0, 0, 0], [0, 0, 0, 0], [100, 50, 0, 255]])
+        );
+    }
+
+    #[test]
+    fn crop_outside() {
+        let recipes = HashMap::from([(
+            Identifier::new("quark", "block/cropped"),
+            recipe(
+                r#"
+                base = "block/glass"
+
+                [[operations]]
+                type = "crop"
+                x = 1
+                y = 0
+                width = 2
+                height = 1
+                "#,
+            ),
+        )]);
+        let textures = HashMap::from([(
+            Identifier::minecraft("block/glass"),
+            image(&[[0, 0, 0, 255], [0, 0, 0, 255]]),
+        )]);
+
+        assert!(resolve_with(recipes, textures).is_err());
+    }
+
+    #[test]
+    fn crop_overflow() {
+        let recipes = HashMap::from([(
+            Identifier::new("quark", "block/cropped"),
+            recipe(
+                r#"
+                base = "block/glass"
+
+                [[operations]]
+                type = "crop"
+                x = 4294967295
+                y = 0
+                width = 2
+                height = 1
+                "#,
+            ),
+        )]);
+        let textures = HashMap::from([(
+            Identifier::minecraft("block/glass"),
+            image(&[[0, 0, 0, 255], [0, 0, 0, 255]]),
+        )]);
+
+        assert!(resolve_with(recipes, textures).is_err());
+    }
+
+    #[test]
+    fn recipe_cycle() {
+        let recipes = HashMap::from([
+            (
+                Identifier::new("quark", "block/a"),
+                recipe(r#"base = "quark:block/b""#),
+            ),
+            (
+                Identifier::new("quark", "block/b"),
+                recipe(r#"base = "quark:block/a""#),
+            ),
+        ]);
+
+        let error = resolve_with(recipes, HashMap::new()).unwrap_err();
+        assert!(error.to_string().contains("cycle"));
+    }
+}
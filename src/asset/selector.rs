@@ -1,5 +1,7 @@
+use glob::{MatchOptions, Pattern};
+use regex::Regex;
 use serde::Deserialize;
-use serde_with::{serde_as, OneOrMany};
+use serde_with::{serde_as, DisplayFromStr, OneOrMany};
 
 use crate::minecraft::asset::types::identifier::Identifier;
 
@@ -19,11 +21,21 @@ impl Default for AssetSelector {
 }
 
 #[serde_as]
-#[derive(Debug, Deserialize, Default, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AssetSelectorQualifier {
-    #[default]
-    All,
+    /// Applies when every qualifier does; without any, applies to everything.
+    All {
+        #[serde(default)]
+        qualifiers: Vec<AssetSelectorQualifier>,
+    },
+    /// Applies when at least one qualifier does.
+    Any {
+        qualifiers: Vec<AssetSelectorQualifier>,
+    },
+    Not {
+        qualifier: Box<AssetSelectorQualifier>,
+    },
     Items {
         #[serde_as(as = "OneOrMany<_>")]
         items: Vec<Identifier>,
@@ -34,12 +46,42 @@ pub enum AssetSelectorQualifier {
         #[serde(default)]
         namespace_specific: bool,
     },
+    /// Glob patterns matched against the path; `*` doesn't match across folders.
+    Glob {
+        #[serde_as(as = "OneOrMany<DisplayFromStr>")]
+        patterns: Vec<Pattern>,
+    },
+    /// A regular expression searched for within the path.
+    Regex {
+        #[serde_as(as = "DisplayFromStr")]
+        pattern: Regex,
+    },
+    Namespace {
+        #[serde_as(as = "OneOrMany<_>")]
+        namespaces: Vec<String>,
+    },
+}
+
+impl Default for AssetSelectorQualifier {
+    fn default() -> Self {
+        Self::All {
+            qualifiers: Vec::new(),
+        }
+    }
 }
 
 impl AssetSelectorQualifier {
+    const GLOB_OPTIONS: MatchOptions = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
     pub fn applies(&self, id: &Identifier) -> bool {
         match self {
-            Self::All => true,
+            Self::All { qualifiers } => qualifiers.iter().all(|qualifier| qualifier.applies(id)),
+            Self::Any { qualifiers } => qualifiers.iter().any(|qualifier| qualifier.applies(id)),
+            Self::Not { qualifier } => !qualifier.applies(id),
             Self::Paths {
                 items,
                 namespace_specific,
@@ -55,6 +97,11 @@ impl AssetSelectorQualifier {
                 }
             }
             Self::Items { items } => items.contains(id),
+            Self::Glob { patterns } => patterns
+                .iter()
+                .any(|pattern| pattern.matches_path_with(&id.path, Self::GLOB_OPTIONS)),
+            Self::Regex { pattern } => pattern.is_match(&id.path.to_string_lossy()),
+            Self::Namespace { namespaces } => namespaces.contains(&id.namespace),
         }
     }
 }
@@ -75,7 +122,7 @@ mod tests {
     #[test]
     fn whitelist_all() {
         let selector = AssetSelector::Whitelist {
-            qualifier: AssetSelectorQualifier::All,
+            qualifier: AssetSelectorQualifier::default(),
         };
         let assets = vec![
             Identifier::minecraft("block/dirt"),
@@ -143,4 +190,87 @@ mod tests {
         assert!(selector.applies(&Identifier::minecraft("block/oak_planks")));
         assert!(!selector.applies(&Identifier::minecraft("block/spruce_planks")));
     }
+
+    fn selector(raw: &str) -> AssetSelector {
+        toml::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn whitelist_glob() {
+        let selector = selector(
+            r#"
+            type = "whitelist"
+            qualifier = { type = "glob", patterns = "block/*_planks" }
+            "#,
+        );
+
+        assert!(selector.applies(&Identifier::minecraft("block/oak_planks")));
+        assert!(selector.applies(&Identifier::new("quark", "block/azalea_planks")));
+        assert!(!selector.applies(&Identifier::minecraft("block/oak_planks/top")));
+        assert!(!selector.applies(&Identifier::minecraft("item/oak_planks")));
+    }
+
+    #[test]
+    fn whitelist_regex() {
+        let selector = selector(
+            r#"
+            type = "whitelist"
+            qualifier = { type = "regex", pattern = "^block/.+_(slab|stairs)$" }
+            "#,
+        );
+
+        assert!(selector.applies(&Identifier::minecraft("block/oak_slab")));
+        assert!(selector.applies(&Identifier::minecraft("block/oak_stairs")));
+        assert!(!selector.applies(&Identifier::minecraft("block/oak_stairs_inner")));
+    }
+
+    #[test]
+    fn blacklist_namespace() {
+        let selector = selector(
+            r#"
+            type = "blacklist"
+            qualifier = { type = "namespace", namespaces = ["minecraft", "quark"] }
+            "#,
+        );
+
+        assert!(!selector.applies(&Identifier::minecraft("block/dirt")));
+        assert!(!selector.applies(&Identifier::new("quark", "block/test")));
+        assert!(selector.applies(&Identifier::new("create", "block/cogwheel")));
+    }
+
+    #[test]
+    fn whitelist_combinators() {
+        let selector = selector(
+            r#"
+            type = "whitelist"
+
+            [qualifier]
+            type = "all"
+            qualifiers = [
+                { type = "any", qualifiers = [
+                    { type = "glob", patterns = "block/*_stairs" },
+                    { type = "glob", patterns = "block/*_stairs_*" },
+                ] },
+                { type = "not", qualifier = { type = "namespace", namespaces = "minecraft" } },
+            ]
+            "#,
+        );
+
+        assert!(selector.applies(&Identifier::new("quark", "block/azalea_stairs")));
+        assert!(selector.applies(&Identifier::new("quark", "block/azalea_stairs_inner")));
+        assert!(!selector.applies(&Identifier::minecraft("block/oak_stairs")));
+        assert!(!selector.applies(&Identifier::new("quark", "block/azalea_slab")));
+    }
+
+    #[test]
+    fn existing_qualifiers_parse() {
+        let selector = selector(
+            r#"
+            type = "whitelist"
+            qualifier = { type = "all" }
+            "#,
+        );
+
+        assert!(selector.applies(&Identifier::new("quark", "block/test")));
+    }
 }
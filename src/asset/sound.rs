@@ -0,0 +1,9 @@
+use crate::minecraft::asset::sound::SoundDefinitions;
+
+use super::LoadableAsset;
+
+impl LoadableAsset for SoundDefinitions {
+    fn load_asset<R: AsRef<str>>(raw: R) -> anyhow::Result<Self> {
+        Ok(serde_json::from_str(raw.as_ref())?)
+    }
+}
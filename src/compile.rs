@@ -1,22 +1,34 @@
-use std::{path::PathBuf, sync::Arc};
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
 
 use anyhow::Context;
 use rand::SeedableRng;
 use rand_pcg::Mcg128Xsl64;
 
 use crate::{
-    config::{CollectionConfig, PackMetaConfig, ProfileConfig},
+    asset::{merge::MergeStrategy, selector::AssetSelector},
+    config::{CollectionConfig, PackMetaConfig, PassthroughConfig, ProfileConfig},
+    minecraft::asset::types::identifier::AssetType,
     sanitize::PathSanitizer,
 };
 
-use self::tracking::AssetTracker;
+use self::{tracking::AssetTracker, vanilla::VanillaAssets};
 
 pub mod compiler;
 pub mod dependency;
 pub mod deploy;
+pub mod flatten;
 pub mod library;
+pub mod minify;
 pub mod modifier;
+pub mod optimize;
+pub mod permutation;
 pub mod tracking;
+pub mod validate;
+pub mod vanilla;
 
 pub struct PackCompiler<'a> {
     project_sanitizer: &'a PathSanitizer,
@@ -28,6 +40,13 @@ pub struct PackCompiler<'a> {
     bundles: Vec<PathBuf>,
     tracker: Arc<AssetTracker>,
     minecraft_versions: Vec<String>,
+    passthrough: PassthroughConfig,
+    merge_strategies: HashMap<AssetType, MergeStrategy>,
+    emulate_paletted_permutations: bool,
+    flatten: Option<AssetSelector>,
+    vanilla: Option<Arc<VanillaAssets>>,
+    /// Cached PNGs read or written by this build; the rest are pruned once it is written.
+    png_cache_used: Mutex<HashSet<PathBuf>>,
     pub rand: Mcg128Xsl64,
 }
 
@@ -39,6 +58,7 @@ impl<'a> PackCompiler<'a> {
         profile: Arc<ProfileConfig>,
         build: CollectionConfig,
         tracker: Arc<AssetTracker>,
+        vanilla: Option<Arc<VanillaAssets>>,
     ) -> anyhow::Result<Self> {
         let name = pack
             .name
@@ -60,6 +80,12 @@ impl<'a> PackCompiler<'a> {
             tracker,
             bundles: Vec::with_capacity(build.bundles.len()),
             minecraft_versions: build.minecraft_versions,
+            passthrough: build.passthrough,
+            merge_strategies: build.merge,
+            emulate_paletted_permutations: build.emulate_paletted_permutations,
+            flatten: build.flatten,
+            vanilla,
+            png_cache_used: Mutex::default(),
         };
 
         for bundle in build.bundles {
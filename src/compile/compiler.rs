@@ -38,9 +38,16 @@ impl PackCompiler<'_> {
     }
 
     async fn run_failable(&mut self) -> anyhow::Result<()> {
-        let mut library = self.populate_asset_library().await?.compile()?;
+        let mut library = self
+            .populate_asset_library()
+            .await?
+            .compile(self.vanilla.as_deref())?;
 
+        self.process_flatten(&mut library);
         self.process_modifiers(&mut library)?;
+        self.process_permutations(&mut library)?;
+        self.validate(&library)?;
+        self.process_minify(&mut library);
 
         self.setup_compile_path().await?;
         self.compile_meta().await?;
@@ -48,6 +55,7 @@ impl PackCompiler<'_> {
         self.compile_license().await?;
 
         library.write_contents(self).await?;
+        self.prune_png_cache().await?;
 
         self.output().await?;
         self.relocate()?;
@@ -76,11 +84,9 @@ impl PackCompiler<'_> {
 
     async fn compile_icon(&self) -> anyhow::Result<()> {
         if let Some(icon) = &self.pack.icon {
-            async_fs::copy(
-                self.project_sanitizer.join(icon)?,
-                &self.compile_path.join(PACK_ICON_NAME),
-            )
-            .await?;
+            let raw = async_fs::read(self.project_sanitizer.join(icon)?).await?;
+            let output = self.optimize_png(raw).await?;
+            async_fs::write(&self.compile_path.join(PACK_ICON_NAME), output).await?;
         }
         Ok(())
     }
@@ -0,0 +1,302 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::minecraft::asset::{
+    model::{ItemDisplayPositions, Model},
+    types::identifier::Identifier,
+};
+
+use super::{library::CompiledAssetLibrary, vanilla::VanillaAssets, PackCompiler};
+
+impl PackCompiler<'_> {
+    /// Inlines the parents of every model that asks for it, so modifiers see its real geometry.
+    pub fn process_flatten(&self, library: &mut CompiledAssetLibrary) {
+        let flattened = library
+            .models
+            .iter()
+            .filter(|(id, model)| {
+                model.mcpacker.flatten
+                    || self
+                        .flatten
+                        .as_ref()
+                        .is_some_and(|selector| selector.applies(id))
+            })
+            .filter_map(|(id, model)| {
+                library
+                    .flatten_model(id, model, self.vanilla.as_deref())
+                    .map(|flattened| (id.clone(), flattened))
+            })
+            .collect::<HashMap<_, _>>();
+
+        library.models.extend(flattened);
+    }
+}
+
+impl CompiledAssetLibrary {
+    /// Merges a model with its parents; children override the textures of their parents.
+    ///
+    /// Elements, each display slot and lighting come from the nearest model that defines them.
+    /// Flattening stops at the first parent that can't be found, which becomes the new parent.
+    /// Returns `None` when there is nothing to inline or the parents form a cycle.
+    fn flatten_model(
+        &self,
+        id: &Identifier,
+        model: &Model,
+        vanilla: Option<&VanillaAssets>,
+    ) -> Option<Model> {
+        let mut flattened = model.clone();
+        let mut visited = HashSet::from([id.clone()]);
+        let mut inlined = false;
+
+        while let Some(parent_id) = flattened.parent.clone() {
+            let Some(parent) = self.lookup_model(&parent_id, vanilla) else {
+                break;
+            };
+
+            if !visited.insert(parent_id) {
+                return None;
+            }
+
+            for (name, texture) in &parent.textures {
+                if !flattened.textures.contains_key(name) {
+                    flattened.textures.insert(name.clone(), texture.clone());
+                }
+            }
+
+            if flattened.elements.is_empty() {
+                flattened.elements.clone_from(&parent.elements);
+            }
+
+            match (&mut flattened.display, &parent.display) {
+                (Some(display), Some(parent_display)) => display.inherit(parent_display),
+                (display @ None, parent_display) => display.clone_from(parent_display),
+                (Some(_), None) => (),
+            }
+
+            if flattened.ambient_occlusion.is_none() {
+                flattened.ambient_occlusion = parent.ambient_occlusion;
+            }
+
+            if flattened.gui_light.is_none() {
+                flattened.gui_light.clone_from(&parent.gui_light);
+            }
+
+            flattened.parent.clone_from(&parent.parent);
+            inlined = true;
+        }
+
+        inlined.then_some(flattened)
+    }
+}
+
+impl ItemDisplayPositions {
+    /// Fills the slots this model leaves out from its parent.
+    fn inherit(&mut self, parent: &Self) {
+        let slots = [
+            (
+                &mut self.thirdperson_righthand,
+                &parent.thirdperson_righthand,
+            ),
+            (&mut self.thirdperson_lefthand, &parent.thirdperson_lefthand),
+            (
+                &mut self.firstperson_righthand,
+                &parent.firstperson_righthand,
+            ),
+            (&mut self.firstperson_lefthand, &parent.firstperson_lefthand),
+            (&mut self.gui, &parent.gui),
+            (&mut self.head, &parent.head),
+            (&mut self.ground, &parent.ground),
+            (&mut self.fixed, &parent.fixed),
+        ];
+
+        for (slot, parent_slot) in slots {
+            if slot.is_none() {
+                slot.clone_from(parent_slot);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        compile::library::tests::{bundle_compiler, write_bundles},
+        config::CollectionConfig,
+    };
+
+    use super::*;
+
+    fn library(models: serde_json::Value) -> CompiledAssetLibrary {
+        let models = serde_json::from_value::<HashMap<String, Model>>(models)
+            .unwrap()
+            .into_iter()
+            .map(|(id, model)| (id.parse().unwrap(), model))
+            .collect();
+
+        CompiledAssetLibrary {
+            models,
+            ..Default::default()
+        }
+    }
+
+    fn flatten(library: &CompiledAssetLibrary, id: &str) -> Option<serde_json::Value> {
+        let id = id.parse().unwrap();
+        let model = library.models.get(&id).unwrap();
+
+        library
+            .flatten_model(&id, model, None)
+            .map(|model| serde_json::to_value(model).unwrap())
+    }
+
+    #[test]
+    fn flatten_parent_chain() {
+        let library = library(json!({
+            "quark:block/cube": {
+                "display": { "gui": { "rotation": [30, 225, 0] } },
+                "textures": { "particle": "#side" },
+                "elements": [{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "faces": { "up": { "texture": "#top" }, "north": { "texture": "#side" } }
+                }]
+            },
+            "quark:block/column": {
+                "parent": "quark:block/cube",
+                "textures": { "side": "quark:block/log", "top": "quark:block/log_top" }
+            },
+            "quark:block/glass_column": {
+                "parent": "quark:block/column",
+                "ambientocclusion": false,
+                "textures": { "side": "quark:block/glass" }
+            }
+        }));
+
+        assert_eq!(
+            flatten(&library, "quark:block/glass_column"),
+            Some(json!({
+                "ambientocclusion": false,
+                "display": { "gui": { "rotation": [30, 225, 0] } },
+                "textures": {
+                    "side": "quark:block/glass",
+                    "top": "quark:block/log_top",
+                    "particle": "#side"
+                },
+                "elements": [{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "faces": { "up": { "texture": "#top" }, "north": { "texture": "#side" } }
+                }]
+            }))
+        );
+    }
+
+    #[test]
+    fn flatten_display_slots() {
+        let library = library(json!({
+            "quark:item/handheld": {
+                "display": {
+                    "gui": { "rotation": [0, 0, 0] },
+                    "ground": { "scale": [0.5, 0.5, 0.5] }
+                }
+            },
+            "quark:item/tool": {
+                "parent": "quark:item/handheld",
+                "display": { "firstperson_righthand": { "rotation": [0, -90, 25] } }
+            },
+            "quark:item/glass_pick": {
+                "parent": "quark:item/tool",
+                "display": { "gui": { "rotation": [30, 225, 0] } }
+            }
+        }));
+
+        assert_eq!(
+            flatten(&library, "quark:item/glass_pick"),
+            Some(json!({
+                "display": {
+                    "firstperson_righthand": { "rotation": [0, -90, 25] },
+                    "gui": { "rotation": [30, 225, 0] },
+                    "ground": { "scale": [0.5, 0.5, 0.5] }
+                }
+            }))
+        );
+    }
+
+    #[test]
+    fn flatten_stops_outside_pack() {
+        let library = library(json!({
+            "quark:block/column": {
+                "parent": "block/cube",
+                "textures": { "side": "quark:block/log" }
+            },
+            "quark:block/glass_column": {
+                "parent": "quark:block/column",
+                "textures": { "side": "quark:block/glass" }
+            }
+        }));
+
+        assert_eq!(
+            flatten(&library, "quark:block/glass_column"),
+            Some(json!({
+                "parent": "block/cube",
+                "textures": { "side": "quark:block/glass" }
+            }))
+        );
+        assert_eq!(flatten(&library, "quark:block/column"), None);
+    }
+
+    #[tokio::test]
+    async fn flatten_model_setting() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(
+            directory.path(),
+            &[
+                (
+                    "base/quark/models/block/cube.json",
+                    r##"{
+                        "elements": [{
+                            "from": [0, 0, 0],
+                            "to": [16, 16, 16],
+                            "faces": { "up": { "texture": "#top" } }
+                        }]
+                    }"##,
+                ),
+                (
+                    "base/quark/models/block/glass.json",
+                    r#"{
+                        "mcpacker": { "flatten": true },
+                        "parent": "quark:block/cube",
+                        "textures": { "top": "quark:block/glass" }
+                    }"#,
+                ),
+            ],
+        );
+        let build = CollectionConfig {
+            bundles: vec!["base".into()],
+            ..Default::default()
+        };
+        let mut compiler = bundle_compiler(&sanitizer, build);
+
+        let mut library = compiler
+            .populate_asset_library()
+            .await
+            .unwrap()
+            .compile(None)
+            .unwrap();
+        compiler.process_flatten(&mut library);
+
+        let glass = &library.models[&Identifier::new("quark", "block/glass")];
+        assert!(glass.parent.is_none());
+        assert_eq!(glass.elements.len(), 1);
+    }
+
+    #[test]
+    fn flatten_cycle() {
+        let library = library(json!({
+            "quark:block/a": { "parent": "quark:block/b" },
+            "quark:block/b": { "parent": "quark:block/a" }
+        }));
+
+        assert_eq!(flatten(&library, "quark:block/a"), None);
+    }
+}
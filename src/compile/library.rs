@@ -1,20 +1,32 @@
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
+    io::Cursor,
     path::{Path, PathBuf},
 };
 
-use anyhow::{anyhow, Context};
+use anyhow::{anyhow, bail, Context};
 use async_fs::File;
 use futures_lite::AsyncWriteExt;
+use image::{ImageFormat, RgbaImage};
 use indexmap::IndexMap;
 use serde::Serialize;
+use serde_json::Value;
 
 use crate::{
-    asset::{model::ModelGeneric, LoadableAsset},
+    asset::{
+        font::FontGeneric,
+        merge::MergeStrategy,
+        model::ModelGeneric,
+        recipe::{self, TextureRecipe},
+        LoadableAsset, MergeableAsset,
+    },
     minecraft::asset::{
         atlas::Atlas,
         blockstate::Blockstate,
+        font::Font,
+        language::{Language, DEFAULT_LANGUAGE},
         model::Model,
+        sound::SoundDefinitions,
         texture::TextureMeta,
         types::{
             identifier::{AssetType, Identifier},
@@ -27,11 +39,10 @@ use crate::{
 use super::{
     dependency::DependencyGraph,
     modifier::{
-        configurable::{
-            ConfigurableModifier, ConfigurableModifierCollection, ConfigurableModifierIndex,
-        },
-        ModelModifiers,
+        configurable::{ConfigurableModifierCollection, ConfigurableModifierIndex},
+        AssetModifiers,
     },
+    vanilla::VanillaAssets,
     PackCompiler,
 };
 
@@ -44,72 +55,108 @@ pub struct AssetLibrary {
     pub atlases: HashMap<Identifier, Atlas>,
     pub textures: HashMap<Identifier, PathBuf>,
     pub textures_meta: HashMap<Identifier, TextureMeta>,
+    pub texture_recipes: HashMap<Identifier, TextureRecipe>,
     pub item_model_definitions: HashMap<Identifier, ItemModelDefinition>,
+    pub sounds: HashMap<Identifier, PathBuf>,
+    pub sound_definitions: HashMap<Identifier, SoundDefinitions>,
+    pub languages: HashMap<Identifier, Language>,
+    pub fonts: HashMap<Identifier, FontGeneric>,
+    /// Unrecognized files copied verbatim; keyed by their path within a bundle.
+    pub passthrough: HashMap<PathBuf, PathBuf>,
 }
 
 impl AssetLibrary {
     pub async fn load_asset(
         &mut self,
-        asset_path: &Path,
+        asset_type: AssetType,
+        id: Identifier,
         asset_path_absolute: &Path,
     ) -> anyhow::Result<()> {
-        let (asset_type, id) = Identifier::from_path(asset_path)?;
-
         match asset_type {
-            AssetType::Model => {
-                Self::load_asset_generic(id, asset_path_absolute, &mut self.models).await
-            }
-            AssetType::Blockstate => {
-                Self::load_asset_generic(id, asset_path_absolute, &mut self.blockstates).await
-            }
             AssetType::Texture => {
                 self.textures.insert(id, asset_path_absolute.to_owned());
                 Ok(())
             }
-            AssetType::Atlas => {
-                Self::load_asset_generic(id, asset_path_absolute, &mut self.atlases).await
-            }
-            AssetType::TextureMeta => {
-                Self::load_asset_generic(id, asset_path_absolute, &mut self.textures_meta).await
+            AssetType::Sound => {
+                self.sounds.insert(id, asset_path_absolute.to_owned());
+                Ok(())
             }
-            AssetType::Modifier => {
-                Self::load_asset_generic(id, asset_path_absolute, &mut self.modifiers).await
+            _ => {
+                let raw = async_fs::read_to_string(asset_path_absolute).await?;
+                self.load_asset_raw(asset_type, id, raw)
             }
-            AssetType::ModifierIndex => {
-                Self::load_asset_single(asset_path_absolute, &mut self.modifier_index).await
+        }
+    }
+
+    pub fn load_asset_raw(
+        &mut self,
+        asset_type: AssetType,
+        id: Identifier,
+        raw: String,
+    ) -> anyhow::Result<()> {
+        match asset_type {
+            AssetType::Model => Self::load_asset_generic(id, raw, &mut self.models),
+            AssetType::Blockstate => Self::load_asset_generic(id, raw, &mut self.blockstates),
+            AssetType::Atlas => Self::load_asset_generic(id, raw, &mut self.atlases),
+            AssetType::Font => Self::load_asset_generic(id, raw, &mut self.fonts),
+            AssetType::TextureMeta => Self::load_asset_generic(id, raw, &mut self.textures_meta),
+            AssetType::TextureRecipe => {
+                Self::load_asset_generic(id, raw, &mut self.texture_recipes)
             }
+            AssetType::Modifier => Self::load_asset_generic(id, raw, &mut self.modifiers),
+            AssetType::ModifierIndex => Self::load_asset_single(raw, &mut self.modifier_index),
             AssetType::ItemModelDefinition => {
-                Self::load_asset_generic(id, asset_path_absolute, &mut self.item_model_definitions)
-                    .await
+                Self::load_asset_generic(id, raw, &mut self.item_model_definitions)
             }
+            AssetType::SoundDefinitions => {
+                Self::load_asset_merged(id, raw, &mut self.sound_definitions)
+            }
+            AssetType::Language => Self::load_asset_merged(id, raw, &mut self.languages),
             _ => Err(anyhow!("Asset type unsupported")),
         }
     }
 
-    async fn load_asset_generic<A: LoadableAsset>(
+    fn load_asset_generic<A: LoadableAsset>(
         id: Identifier,
-        path: impl AsRef<Path>,
+        raw: String,
         store: &mut HashMap<Identifier, A>,
     ) -> anyhow::Result<()> {
-        let raw = async_fs::read_to_string(path).await?;
         let parsed = A::load_asset(raw)?;
         store.insert(id, parsed);
 
         Ok(())
     }
 
-    async fn load_asset_single<A: LoadableAsset>(
-        path: impl AsRef<Path>,
-        store: &mut A,
+    fn load_asset_merged<A: LoadableAsset + MergeableAsset>(
+        id: Identifier,
+        raw: String,
+        store: &mut HashMap<Identifier, A>,
     ) -> anyhow::Result<()> {
-        let raw = async_fs::read_to_string(path).await?;
+        let parsed = A::load_asset(raw)?;
+
+        if let Some(existing) = store.get_mut(&id) {
+            existing.merge(parsed);
+        } else {
+            store.insert(id, parsed);
+        }
+
+        Ok(())
+    }
+
+    fn load_asset_single<A: LoadableAsset>(raw: String, store: &mut A) -> anyhow::Result<()> {
         let parsed = A::load_asset(raw)?;
         *store = parsed;
 
         Ok(())
     }
 
-    pub fn compile(mut self) -> anyhow::Result<CompiledAssetLibrary> {
+    pub fn compile(
+        mut self,
+        vanilla: Option<&VanillaAssets>,
+    ) -> anyhow::Result<CompiledAssetLibrary> {
+        self.check_sound_files()?;
+        self.check_language_keys();
+
         let model_graph = DependencyGraph::from(&self.models).sort()?;
 
         let mut compiled_models = HashMap::with_capacity(self.models.len());
@@ -133,12 +180,37 @@ impl AssetLibrary {
             .filter(|(_, model)| !model.is_virtual())
         {
             let compiled_model =
-                preprocessed_model.compile(&compiled_models, &preprocessed_models)?;
+                preprocessed_model.compile(&compiled_models, &preprocessed_models, vanilla)?;
 
             compiled_models.insert(preprocessed_model_id.clone(), compiled_model);
         }
 
-        let mut modifiers = Vec::new();
+        let mut generated_textures =
+            recipe::resolve_recipes(&self.texture_recipes, &self.textures, vanilla)?;
+
+        let mut compiled_fonts = HashMap::with_capacity(self.fonts.len());
+        let mut glyph_textures = HashSet::new();
+
+        for (font_id, font_generic) in self.fonts {
+            let font = match font_generic {
+                FontGeneric::Normal(font) => font,
+                FontGeneric::Preprocessed(font) => {
+                    glyph_textures.extend(font.glyph_textures());
+                    let (font, sheets) = font
+                        .compile(&self.textures, vanilla)
+                        .with_context(|| format!("Failed to compile font: {}", font_id))?;
+                    generated_textures.extend(sheets);
+                    font
+                }
+            };
+
+            compiled_fonts.insert(font_id, font);
+        }
+
+        // Glyphs only exist to be stitched into their sheets.
+        self.textures.retain(|id, _| !glyph_textures.contains(id));
+
+        let mut modifiers = AssetModifiers::default();
 
         for modifier_collection_id in &self.modifier_index.order {
             let modifier_collection = self
@@ -152,8 +224,7 @@ impl AssetLibrary {
                 })?
                 .modifiers
                 .iter()
-                .cloned()
-                .map(ConfigurableModifier::into);
+                .cloned();
             modifiers.extend(modifier_collection);
         }
 
@@ -165,8 +236,59 @@ impl AssetLibrary {
             textures: self.textures,
             textures_meta: self.textures_meta,
             item_model_definitions: self.item_model_definitions,
+            sounds: self.sounds,
+            sound_definitions: self.sound_definitions,
+            languages: self.languages,
+            fonts: compiled_fonts,
+            generated_textures,
+            passthrough: self.passthrough,
         })
     }
+
+    fn check_sound_files(&self) -> anyhow::Result<()> {
+        let missing = self
+            .sound_definitions
+            .values()
+            .flat_map(SoundDefinitions::sound_files)
+            .filter(|(_, file)| !self.sounds.contains_key(file))
+            .map(|(event_name, file)| format!("{} -> {}", event_name, file))
+            .collect::<Vec<_>>();
+
+        if !missing.is_empty() {
+            bail!(
+                "Sound events reference missing files:\n{}",
+                missing.join("\n")
+            );
+        }
+
+        Ok(())
+    }
+
+    fn check_language_keys(&self) {
+        for (language_id, language) in &self.languages {
+            if language_id.path == Path::new(DEFAULT_LANGUAGE) {
+                continue;
+            }
+
+            let reference_id = Identifier::new(&language_id.namespace, DEFAULT_LANGUAGE);
+
+            if let Some(reference) = self.languages.get(&reference_id) {
+                let missing = language
+                    .missing_keys(reference)
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                if !missing.is_empty() {
+                    println!(
+                        "[WARNING] Language \"{}\" is missing keys from \"{}\":\n{}",
+                        language_id,
+                        reference_id,
+                        missing.join("\n")
+                    );
+                }
+            }
+        }
+    }
 }
 
 impl PackCompiler<'_> {
@@ -174,42 +296,158 @@ impl PackCompiler<'_> {
         let tracked_files = self.tracker.condence(&self.bundles)?;
         let mut library = AssetLibrary::default();
 
-        for file in tracked_files {
-            let asset_path = file
-                .strip_prefix(&self.bundles_path)?
-                .iter()
-                .skip(1)
-                .collect::<PathBuf>();
+        for (asset_path, mut copies) in tracked_files {
+            let (asset_type, id) = match Identifier::from_path(&asset_path) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    let file = copies.pop().with_context(|| {
+                        format!("Failed to find tracked file: {}", asset_path.display())
+                    })?;
+
+                    if self.passthrough.allows(&asset_path) {
+                        library.passthrough.insert(asset_path, file);
+                    } else {
+                        println!("[WARNING] Excluded file at \"{}\":\n{}", file.display(), e);
+                    }
 
-            if let Err(e) = library.load_asset(&asset_path, &file).await {
-                println!("[WARNING] Parse error at \"{}\":\n{}", file.display(), e);
+                    continue;
+                }
+            };
+
+            if asset_type.has_merge_strategy() {
+                let merged = self
+                    .merge_copies(&asset_type, &copies)
+                    .await
+                    .and_then(|raw| library.load_asset_raw(asset_type, id, raw));
+
+                if let Err(e) = merged {
+                    println!(
+                        "[WARNING] Parse error at \"{}\":\n{}",
+                        asset_path.display(),
+                        e
+                    );
+                }
+
+                continue;
+            }
+
+            // Only mergeable assets look past the overriding copy.
+            if !asset_type.is_mergeable() {
+                copies.drain(..copies.len() - 1);
+            }
+
+            for file in copies {
+                if let Err(e) = library
+                    .load_asset(asset_type.clone(), id.clone(), &file)
+                    .await
+                {
+                    println!("[WARNING] Parse error at \"{}\":\n{}", file.display(), e);
+                }
             }
         }
 
         Ok(library)
     }
+
+    /// Combines every bundle's copy of an asset, starting from the first bundle.
+    ///
+    /// Copies before the last one that overrides are never read, like other assets. When nothing
+    /// is merged into the last copy, its text is kept so keys stay in order and errors point
+    /// into the file.
+    async fn merge_copies(
+        &self,
+        asset_type: &AssetType,
+        copies: &[PathBuf],
+    ) -> anyhow::Result<String> {
+        let default_strategy = self
+            .merge_strategies
+            .get(asset_type)
+            .copied()
+            .unwrap_or_default();
+        let mut layers = Vec::new();
+
+        for file in copies.iter().rev() {
+            let raw = async_fs::read_to_string(file).await?;
+            let mut value = serde_json::from_str::<Value>(&raw)
+                .with_context(|| format!("Failed to parse JSON: {}", file.display()))?;
+            let strategy = MergeStrategy::take_marker(&mut value)
+                .with_context(|| format!("Invalid merge marker: {}", file.display()))?
+                .unwrap_or(default_strategy);
+
+            if layers.is_empty() && (strategy == MergeStrategy::Override || copies.len() == 1) {
+                return Ok(raw);
+            }
+
+            layers.push((strategy, value));
+
+            if strategy == MergeStrategy::Override {
+                break;
+            }
+        }
+
+        let mut merged = Value::Null;
+
+        for (strategy, value) in layers.into_iter().rev() {
+            strategy.merge(&mut merged, value);
+        }
+
+        Ok(serde_json::to_string(&merged)?)
+    }
 }
 
+#[derive(Default)]
 pub struct CompiledAssetLibrary {
     pub models: HashMap<Identifier, Model>,
-    pub modifiers: ModelModifiers,
+    pub modifiers: AssetModifiers,
     pub blockstates: HashMap<Identifier, Blockstate>,
     pub atlases: HashMap<Identifier, Atlas>,
     pub textures: HashMap<Identifier, PathBuf>,
     pub textures_meta: HashMap<Identifier, TextureMeta>,
     pub item_model_definitions: HashMap<Identifier, ItemModelDefinition>,
+    pub sounds: HashMap<Identifier, PathBuf>,
+    pub sound_definitions: HashMap<Identifier, SoundDefinitions>,
+    pub languages: HashMap<Identifier, Language>,
+    pub fonts: HashMap<Identifier, Font>,
+    pub generated_textures: HashMap<Identifier, RgbaImage>,
+    pub passthrough: HashMap<PathBuf, PathBuf>,
 }
 
 impl CompiledAssetLibrary {
+    /// Finds a model in the pack, falling back to the vanilla assets.
+    pub fn lookup_model<'a>(
+        &'a self,
+        id: &Identifier,
+        vanilla: Option<&'a VanillaAssets>,
+    ) -> Option<&'a Model> {
+        self.models
+            .get(id)
+            .or_else(|| vanilla.and_then(|vanilla| vanilla.models.get(id)))
+    }
+
     pub async fn write_contents(&self, compiler: &PackCompiler<'_>) -> anyhow::Result<()> {
         Self::write_asset_collection(compiler, &self.models).await?;
         Self::write_asset_collection(compiler, &self.blockstates).await?;
         Self::write_asset_collection(compiler, &self.atlases).await?;
         Self::write_asset_collection(compiler, &self.textures_meta).await?;
         Self::write_asset_collection(compiler, &self.item_model_definitions).await?;
+        Self::write_asset_collection(compiler, &self.sound_definitions).await?;
+        Self::write_asset_collection(compiler, &self.languages).await?;
+        Self::write_asset_collection(compiler, &self.fonts).await?;
 
         for (id, texture) in &self.textures {
-            Self::copy_asset(compiler, id, texture, &AssetType::Texture).await?;
+            Self::copy_texture(compiler, id, texture).await?;
+        }
+
+        for (id, sound) in &self.sounds {
+            Self::copy_asset(compiler, id, sound, &AssetType::Sound).await?;
+        }
+
+        for (id, texture) in &self.generated_textures {
+            Self::write_texture(compiler, id, texture).await?;
+        }
+
+        for (asset_path, file) in &self.passthrough {
+            Self::copy_passthrough(compiler, asset_path, file).await?;
         }
 
         Ok(())
@@ -254,6 +492,84 @@ impl CompiledAssetLibrary {
         Ok(())
     }
 
+    async fn write_texture(
+        compiler: &PackCompiler<'_>,
+        id: &Identifier,
+        texture: &RgbaImage,
+    ) -> anyhow::Result<()> {
+        let output_file_path =
+            id.to_path(&compiler.compile_path.join("assets"), &AssetType::Texture);
+
+        let output_folder = output_file_path.parent().with_context(|| {
+            format!(
+                "Failed to get asset folder path from: {}",
+                output_file_path.display()
+            )
+        })?;
+        async_fs::create_dir_all(output_folder).await?;
+
+        let mut output = Cursor::new(Vec::new());
+        texture.write_to(&mut output, ImageFormat::Png)?;
+        let output = compiler.optimize_png(output.into_inner()).await?;
+
+        let mut output_file = File::create(output_file_path).await?;
+        output_file.write_all(&output).await?;
+        output_file.flush().await?;
+
+        Ok(())
+    }
+
+    async fn copy_texture(
+        compiler: &PackCompiler<'_>,
+        id: &Identifier,
+        texture: &Path,
+    ) -> anyhow::Result<()> {
+        if !compiler.profile.optimize_png {
+            return Self::copy_asset(compiler, id, texture, &AssetType::Texture).await;
+        }
+
+        let output_file_path =
+            id.to_path(&compiler.compile_path.join("assets"), &AssetType::Texture);
+
+        let output_folder = output_file_path.parent().with_context(|| {
+            format!(
+                "Failed to get asset folder path from: {}",
+                output_file_path.display()
+            )
+        })?;
+        async_fs::create_dir_all(output_folder).await?;
+
+        let raw = async_fs::read(texture).await?;
+        let output = compiler
+            .optimize_png(raw)
+            .await
+            .with_context(|| format!("Failed to optimize texture: {}", id))?;
+
+        async_fs::write(output_file_path, output).await?;
+
+        Ok(())
+    }
+
+    async fn copy_passthrough(
+        compiler: &PackCompiler<'_>,
+        asset_path: &Path,
+        file: &Path,
+    ) -> anyhow::Result<()> {
+        let output_file_path = compiler.compile_path.join("assets").join(asset_path);
+
+        let output_folder = output_file_path.parent().with_context(|| {
+            format!(
+                "Failed to get asset folder path from: {}",
+                output_file_path.display()
+            )
+        })?;
+        async_fs::create_dir_all(output_folder).await?;
+
+        async_fs::copy(file, output_file_path).await?;
+
+        Ok(())
+    }
+
     async fn copy_asset<P: AsRef<Path>>(
         compiler: &PackCompiler<'_>,
         id: &Identifier,
@@ -275,3 +591,185 @@ impl CompiledAssetLibrary {
         Ok(())
     }
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::sync::Arc;
+
+    use crate::{
+        compile::tracking::AssetTracker,
+        config::{CollectionConfig, PackMetaConfig, ProfileConfig},
+        sanitize::PathSanitizer,
+    };
+
+    use super::*;
+
+    /// Writes files into the bundles folder of a project in `directory`.
+    ///
+    /// Paths start with the bundle name, like `base/quark/models/block/glass.json`.
+    pub(crate) fn write_bundles(directory: &Path, files: &[(&str, &str)]) -> PathSanitizer {
+        for (path, contents) in files {
+            let path = directory.join("src").join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, contents).unwrap();
+        }
+
+        PathSanitizer::try_from(directory.to_path_buf()).unwrap()
+    }
+
+    pub(crate) fn bundle_compiler(
+        sanitizer: &PathSanitizer,
+        build: CollectionConfig,
+    ) -> PackCompiler<'_> {
+        let mut tracker = AssetTracker::default();
+        tracker
+            .search_bundle_files(&sanitizer.restricted_path.join("src"))
+            .unwrap();
+
+        let pack = PackMetaConfig {
+            name: Some("test".into()),
+            ..Default::default()
+        };
+
+        PackCompiler::new(
+            sanitizer,
+            sanitizer.restricted_path.join("minecraft"),
+            pack,
+            Arc::new(ProfileConfig::default()),
+            build,
+            Arc::new(tracker),
+            None,
+        )
+        .unwrap()
+    }
+
+    fn bundles(names: &[&str]) -> CollectionConfig {
+        CollectionConfig {
+            bundles: names.iter().map(PathBuf::from).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn merge_marker_keeps_model_settings() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(
+            directory.path(),
+            &[
+                (
+                    "base/quark/models/block/template.json",
+                    r#"{ "textures": { "side": "quark:block/glass" } }"#,
+                ),
+                (
+                    "addon/quark/models/block/template.json",
+                    r##"{
+                        "mcpacker": { "virtual": true },
+                        "mcpacker_merge": { "strategy": "deep_merge" },
+                        "textures": { "top": "#side" }
+                    }"##,
+                ),
+            ],
+        );
+        let mut compiler = bundle_compiler(&sanitizer, bundles(&["base", "addon"]));
+
+        let library = compiler.populate_asset_library().await.unwrap();
+        let Some(ModelGeneric::Normal(model)) = library
+            .models
+            .get(&Identifier::new("quark", "block/template"))
+        else {
+            panic!("Model wasn't loaded");
+        };
+
+        assert!(model.is_virtual());
+        assert_eq!(model.textures.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn overriding_copy_keeps_key_order() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(
+            directory.path(),
+            &[
+                ("base/quark/models/block/glass.json", "{}"),
+                (
+                    "addon/quark/models/block/glass.json",
+                    r##"{ "textures": { "top": "quark:block/glass", "side": "#top", "all": "#side" } }"##,
+                ),
+            ],
+        );
+        let mut compiler = bundle_compiler(&sanitizer, bundles(&["base", "addon"]));
+
+        let library = compiler.populate_asset_library().await.unwrap();
+        let Some(ModelGeneric::Normal(model)) =
+            library.models.get(&Identifier::new("quark", "block/glass"))
+        else {
+            panic!("Model wasn't loaded");
+        };
+
+        assert_eq!(
+            model.textures.keys().collect::<Vec<_>>(),
+            ["top", "side", "all"]
+        );
+    }
+
+    #[tokio::test]
+    async fn glyph_textures_are_not_kept() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(
+            directory.path(),
+            &[(
+                "base/quark/font/icons.json",
+                r#"{
+                    "glyph_sheets": [{
+                        "file": "quark:font/icons.png",
+                        "glyphs": "quark:font/icons",
+                        "chars": { "\ue000": "heart" },
+                        "ascent": 7
+                    }]
+                }"#,
+            )],
+        );
+        let glyph_path = directory
+            .path()
+            .join("src/base/quark/textures/font/icons/heart.png");
+        std::fs::create_dir_all(glyph_path.parent().unwrap()).unwrap();
+        RgbaImage::new(2, 2).save(glyph_path).unwrap();
+
+        let mut compiler = bundle_compiler(&sanitizer, bundles(&["base"]));
+        let library = compiler
+            .populate_asset_library()
+            .await
+            .unwrap()
+            .compile(None)
+            .unwrap();
+
+        assert!(!library
+            .textures
+            .contains_key(&Identifier::new("quark", "font/icons/heart")));
+        assert!(library
+            .generated_textures
+            .contains_key(&Identifier::new("quark", "font/icons")));
+    }
+
+    #[tokio::test]
+    async fn overridden_copies_are_not_parsed() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(
+            directory.path(),
+            &[
+                ("base/quark/blockstates/glass.json", "{ broken"),
+                (
+                    "addon/quark/blockstates/glass.json",
+                    r#"{ "variants": { "": { "model": "quark:block/glass" } } }"#,
+                ),
+            ],
+        );
+        let mut compiler = bundle_compiler(&sanitizer, bundles(&["base", "addon"]));
+
+        let library = compiler.populate_asset_library().await.unwrap();
+
+        assert!(library
+            .blockstates
+            .contains_key(&Identifier::new("quark", "glass")));
+    }
+}
@@ -0,0 +1,265 @@
+use crate::{
+    config::MinifyConfig,
+    minecraft::asset::{
+        blockstate::{Blockstate, ModelState},
+        model::{ElementRotation, GuiLightDirection, ItemDisplay, Model},
+        types::{
+            item::{ItemModelDefinition, ModelType},
+            vec::{Vec3, Vec4},
+        },
+    },
+};
+
+use super::{library::CompiledAssetLibrary, PackCompiler};
+
+pub trait Minify {
+    /// Shrinks the serialized output without changing its meaning.
+    fn minify(&mut self, config: &MinifyConfig);
+}
+
+impl PackCompiler<'_> {
+    pub fn process_minify(&self, library: &mut CompiledAssetLibrary) {
+        if let Some(config) = &self.profile.minify {
+            library
+                .models
+                .values_mut()
+                .for_each(|model| model.minify(config));
+            library
+                .blockstates
+                .values_mut()
+                .for_each(|blockstate| blockstate.minify(config));
+            library
+                .item_model_definitions
+                .values_mut()
+                .for_each(|item| item.minify(config));
+        }
+    }
+}
+
+impl MinifyConfig {
+    pub fn round(&self, value: f32) -> f32 {
+        let factor = 10_f64.powi(self.precision as i32);
+        ((value as f64 * factor).round() / factor) as f32
+    }
+
+    fn round_vec3(&self, value: &mut Vec3) {
+        let (x, y, z) = value.into();
+
+        for axis in [x, y, z] {
+            *axis = self.round(*axis);
+        }
+    }
+
+    fn round_vec4(&self, value: &mut Vec4) {
+        let (w, x, y, z) = value.into();
+
+        for axis in [w, x, y, z] {
+            *axis = self.round(*axis);
+        }
+    }
+}
+
+impl Minify for Model {
+    fn minify(&mut self, config: &MinifyConfig) {
+        for element in &mut self.elements {
+            config.round_vec3(&mut element.from);
+            config.round_vec3(&mut element.to);
+
+            if let Some(rotation) = &mut element.rotation {
+                config.round_vec3(&mut rotation.origin);
+                rotation.angle = config.round(rotation.angle);
+            }
+
+            // A rotation of zero degrees doesn't rescale either.
+            if element
+                .rotation
+                .as_ref()
+                .is_some_and(ElementRotation::is_zero)
+            {
+                element.rotation = None;
+            }
+
+            for face in (&mut element.faces).into_iter().flatten() {
+                if let Some(uv) = &mut face.uv {
+                    config.round_vec4(uv);
+                }
+            }
+        }
+
+        if let Some(display) = &mut self.display {
+            for item_display in display.into_iter().flatten() {
+                item_display.minify(config);
+            }
+        }
+
+        // Children inherit these from their parent; only a root model can omit them.
+        if self.parent.is_none() {
+            if self.ambient_occlusion == Some(true) {
+                self.ambient_occlusion = None;
+            }
+
+            if self.gui_light == Some(GuiLightDirection::default()) {
+                self.gui_light = None;
+            }
+        }
+    }
+}
+
+impl Minify for ItemDisplay {
+    fn minify(&mut self, config: &MinifyConfig) {
+        let fields = [
+            (&mut self.rotation, Vec3::default()),
+            (&mut self.translation, Vec3::default()),
+            (&mut self.scale, Vec3::new(1.0, 1.0, 1.0)),
+        ];
+
+        for (field, default) in fields {
+            if let Some(value) = field {
+                config.round_vec3(value);
+            }
+
+            if *field == Some(default) {
+                *field = None;
+            }
+        }
+    }
+}
+
+impl Minify for Blockstate {
+    fn minify(&mut self, config: &MinifyConfig) {
+        match self {
+            Self::Variants(variants) => {
+                variants.values_mut().for_each(|state| state.minify(config))
+            }
+            Self::Multipart(parts) => parts.iter_mut().for_each(|part| part.apply.minify(config)),
+        }
+    }
+}
+
+impl Minify for ModelState {
+    fn minify(&mut self, _config: &MinifyConfig) {
+        // The weight of a lone state is meaningless.
+        if let Self::Weighted(states) = self {
+            if let [state] = states.as_slice() {
+                *self = Self::Single {
+                    model: state.model.clone(),
+                    x: state.x.clone(),
+                    y: state.y.clone(),
+                    uvlock: state.uvlock,
+                };
+            }
+        }
+    }
+}
+
+impl Minify for ItemModelDefinition {
+    fn minify(&mut self, _config: &MinifyConfig) {
+        unwrap_composites(&mut self.model);
+    }
+}
+
+/// A composite of one model is that model.
+fn unwrap_composites(model: &mut ModelType) {
+    for child in model.children_mut() {
+        unwrap_composites(child);
+    }
+
+    if let ModelType::Composite { models } = model {
+        if models.len() == 1 {
+            if let Some(only) = models.pop() {
+                *model = only;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn minify<T: Minify + serde::de::DeserializeOwned + serde::Serialize>(
+        value: serde_json::Value,
+    ) -> serde_json::Value {
+        let mut asset: T = serde_json::from_value(value).unwrap();
+        asset.minify(&MinifyConfig { precision: 4 });
+        serde_json::to_value(&asset).unwrap()
+    }
+
+    #[test]
+    fn round_float_artifacts() {
+        let config = MinifyConfig { precision: 4 };
+        assert_eq!(config.round(0.1 + 0.2), 0.3);
+        assert_eq!(config.round(22.500002), 22.5);
+    }
+
+    #[test]
+    fn minify_model() {
+        let minified = minify::<Model>(json!({
+            "ambientocclusion": true,
+            "gui_light": "side",
+            "display": {
+                "gui": { "rotation": [30, 225, 0], "translation": [0, 0, 0], "scale": [1, 1, 1] }
+            },
+            "elements": [{
+                "from": [0.50000006, 0, 0],
+                "to": [16, 15.999999046325684, 16],
+                "rotation": { "origin": [8, 8, 8], "axis": "y", "angle": 0, "rescale": true },
+                "faces": { "up": { "texture": "#top", "uv": [0, 0, 16.00000190734863, 16] } }
+            }]
+        }));
+
+        assert_eq!(
+            minified,
+            json!({
+                "display": { "gui": { "rotation": [30, 225, 0] } },
+                "elements": [{
+                    "from": [0.5, 0, 0],
+                    "to": [16, 16, 16],
+                    "faces": { "up": { "texture": "#top", "uv": [0, 0, 16, 16] } }
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn minify_model_keeps_inherited() {
+        let minified = minify::<Model>(json!({
+            "parent": "block/cube",
+            "ambientocclusion": true
+        }));
+
+        assert_eq!(
+            minified,
+            json!({ "parent": "block/cube", "ambientocclusion": true })
+        );
+    }
+
+    #[test]
+    fn minify_blockstate_single_weighted() {
+        let minified = minify::<Blockstate>(json!({
+            "variants": { "": [{ "model": "block/stone", "y": 90, "weight": 3 }] }
+        }));
+
+        assert_eq!(
+            minified,
+            json!({ "variants": { "": { "model": "block/stone", "y": 90 } } })
+        );
+    }
+
+    #[test]
+    fn minify_item_single_composite() {
+        let minified = minify::<ItemModelDefinition>(json!({
+            "model": {
+                "type": "composite",
+                "models": [{ "type": "model", "model": "item/stick" }]
+            }
+        }));
+
+        assert_eq!(
+            minified,
+            json!({ "model": { "type": "model", "model": "item/stick" } })
+        );
+    }
+}
@@ -1,13 +1,33 @@
-use crate::minecraft::asset::{model::Model, types::identifier::Identifier};
+use crate::minecraft::asset::{
+    blockstate::Blockstate,
+    model::Model,
+    types::{identifier::Identifier, item::ItemModelDefinition},
+};
 
 use super::{library::CompiledAssetLibrary, PackCompiler};
 
 pub mod configurable;
 pub mod culling;
+pub mod hand_animation;
+pub mod merge;
 pub mod redirect;
+pub mod rotation;
+pub mod uvlock;
+pub mod wrap;
 pub mod zfighting;
 
 pub type ModelModifiers = Vec<Box<dyn Modifier<Model, Identifier> + Send + Sync>>;
+pub type BlockstateModifiers = Vec<Box<dyn Modifier<Blockstate, Identifier> + Send + Sync>>;
+pub type ItemModelDefinitionModifiers =
+    Vec<Box<dyn Modifier<ItemModelDefinition, Identifier> + Send + Sync>>;
+
+/// Modifiers grouped by the asset type they run on, in the order they were listed.
+#[derive(Default)]
+pub struct AssetModifiers {
+    pub models: ModelModifiers,
+    pub blockstates: BlockstateModifiers,
+    pub item_model_definitions: ItemModelDefinitionModifiers,
+}
 
 pub trait Modifier<A, S> {
     fn apply_modifier(&self, asset: &mut A, compiler: &mut PackCompiler);
@@ -21,6 +41,8 @@ pub trait Modifier<A, S> {
 impl PackCompiler<'_> {
     pub fn process_modifiers(&mut self, library: &mut CompiledAssetLibrary) -> anyhow::Result<()> {
         library.apply_model_modifiers(self);
+        library.apply_blockstate_modifiers(self);
+        library.apply_item_model_definition_modifiers(self);
 
         Ok(())
     }
@@ -30,9 +52,34 @@ impl CompiledAssetLibrary {
     fn apply_model_modifiers(&mut self, compiler: &mut PackCompiler) {
         self.models.iter_mut().for_each(|(model_id, model)| {
             self.modifiers
+                .models
                 .iter()
                 .filter(|modifier| modifier.does_modifier_apply(model_id))
                 .for_each(|modifier| modifier.apply_modifier(model, compiler))
         });
     }
+
+    fn apply_blockstate_modifiers(&mut self, compiler: &mut PackCompiler) {
+        self.blockstates
+            .iter_mut()
+            .for_each(|(blockstate_id, blockstate)| {
+                self.modifiers
+                    .blockstates
+                    .iter()
+                    .filter(|modifier| modifier.does_modifier_apply(blockstate_id))
+                    .for_each(|modifier| modifier.apply_modifier(blockstate, compiler))
+            });
+    }
+
+    fn apply_item_model_definition_modifiers(&mut self, compiler: &mut PackCompiler) {
+        self.item_model_definitions
+            .iter_mut()
+            .for_each(|(item_id, item)| {
+                self.modifiers
+                    .item_model_definitions
+                    .iter()
+                    .filter(|modifier| modifier.does_modifier_apply(item_id))
+                    .for_each(|modifier| modifier.apply_modifier(item, compiler))
+            });
+    }
 }
@@ -4,13 +4,16 @@ use serde_with::{serde_as, OneOrMany};
 use crate::{
     asset::LoadableAsset,
     minecraft::asset::{
-        model::Model,
         types::identifier::{AssetType, Identifier},
         Asset,
     },
 };
 
-use super::{culling::CullingModifier, redirect::Redirect, zfighting::ZFightingModifier, Modifier};
+use super::{
+    culling::CullingModifier, hand_animation::HandAnimationModifier, merge::MergeModifier,
+    redirect::Redirect, rotation::RandomYRotationModifier, uvlock::UvlockModifier,
+    wrap::ItemWrapModifier, zfighting::ZFightingModifier, AssetModifiers,
+};
 
 #[serde_as]
 #[derive(Debug, Default, Deserialize)]
@@ -56,14 +59,34 @@ pub enum ConfigurableModifier {
     ZFighting(Box<ZFightingModifier>),
     Redirect(Box<Redirect>),
     Culling(Box<CullingModifier>),
+    RandomYRotation(Box<RandomYRotationModifier>),
+    Uvlock(Box<UvlockModifier>),
+    ItemWrap(Box<ItemWrapModifier>),
+    HandAnimation(Box<HandAnimationModifier>),
+    Merge(Box<MergeModifier>),
 }
 
-impl From<ConfigurableModifier> for Box<dyn Modifier<Model, Identifier> + Send + Sync> {
-    fn from(value: ConfigurableModifier) -> Self {
-        match value {
-            ConfigurableModifier::ZFighting(modifier) => modifier,
-            ConfigurableModifier::Redirect(modifier) => modifier,
-            ConfigurableModifier::Culling(modifier) => modifier,
+impl Extend<ConfigurableModifier> for AssetModifiers {
+    fn extend<T: IntoIterator<Item = ConfigurableModifier>>(&mut self, iter: T) {
+        for modifier in iter {
+            match modifier {
+                ConfigurableModifier::ZFighting(modifier) => self.models.push(modifier),
+                ConfigurableModifier::Redirect(modifier) => {
+                    self.models.push(modifier.clone());
+                    self.blockstates.push(modifier.clone());
+                    self.item_model_definitions.push(modifier);
+                }
+                ConfigurableModifier::Culling(modifier) => self.models.push(modifier),
+                ConfigurableModifier::RandomYRotation(modifier) => self.blockstates.push(modifier),
+                ConfigurableModifier::Uvlock(modifier) => self.blockstates.push(modifier),
+                ConfigurableModifier::ItemWrap(modifier) => {
+                    self.item_model_definitions.push(modifier)
+                }
+                ConfigurableModifier::HandAnimation(modifier) => {
+                    self.item_model_definitions.push(modifier)
+                }
+                ConfigurableModifier::Merge(modifier) => self.models.push(modifier),
+            }
         }
     }
 }
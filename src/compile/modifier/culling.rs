@@ -4,24 +4,52 @@ use crate::{
     asset::selector::AssetSelector,
     compile::PackCompiler,
     minecraft::asset::{
-        model::{Model, ModelElement},
-        types::identifier::Identifier,
+        model::{Axis, ElementFaces, Model, ModelElement},
+        types::{identifier::Identifier, vec::Vec3},
     },
 };
 
-use super::Modifier;
+use super::{zfighting::Direction, Modifier};
 
-#[derive(Debug, Default, Deserialize, Clone)]
+/// Tolerance for corners of rotated elements, which rarely land exactly on a neighbour's bounds.
+const ENCLOSED_EPSILON: f32 = 1e-4;
+
+#[derive(Debug, Default, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CullingMode {
+    /// Only removes faces that are completely hidden.
+    #[default]
+    Full,
+    /// Also splits partially hidden faces into elements that cover only the visible part.
+    Split,
+}
+
+#[derive(Debug, Deserialize, Clone)]
 pub struct CullingModifier {
+    #[serde(default)]
+    mode: CullingMode,
+    /// Faces stop being split once the model would have more elements than this.
+    #[serde(default = "CullingModifier::default_max_elements")]
+    max_elements: usize,
     #[serde(default)]
     selector: AssetSelector,
 }
 
+impl CullingModifier {
+    #[inline]
+    fn default_max_elements() -> usize {
+        128
+    }
+}
+
 impl Modifier<Model, Identifier> for CullingModifier {
     fn apply_modifier(&self, asset: &mut Model, _compiler: &mut PackCompiler) {
-        asset.scan_filter_elements(|primary_element, scan_element| {
-            primary_element.cull_faces(scan_element)
-        });
+        match self.mode {
+            CullingMode::Full => asset.scan_filter_elements(|primary_element, scan_element| {
+                primary_element.cull_faces(scan_element)
+            }),
+            CullingMode::Split => asset.split_cull_faces(self.max_elements),
+        }
     }
 
     fn does_modifier_apply(&self, id: &Identifier) -> bool {
@@ -32,13 +60,12 @@ impl Modifier<Model, Identifier> for CullingModifier {
 impl ModelElement {
     // Returns `true` when all faces are empty.
     fn cull_faces(&mut self, other: &Self) -> bool {
-        let is_rotation_zero = if let Some(rotation) = &self.rotation {
-            rotation.is_zero()
-        } else {
-            true
-        };
+        if self.is_enclosed_by(other) {
+            self.faces = ElementFaces::default();
+        }
 
-        if is_rotation_zero {
+        // Elements rotated the same way can be compared as if neither was rotated.
+        if self.shares_frame(other) {
             let (from_x, from_y, from_z) = (&self.from).into();
             let (to_x, to_y, to_z) = (&self.to).into();
 
@@ -108,3 +135,451 @@ impl ModelElement {
         (&self.faces).into_iter().all(|face| face.is_none())
     }
 }
+
+impl Model {
+    /// Removes the hidden parts of faces, splitting off an element for each visible part.
+    ///
+    /// Faces are only culled against elements with the same rotation. Splitting stops before
+    /// the model grows past `max_elements`; faces that would exceed it are left as they are.
+    fn split_cull_faces(&mut self, max_elements: usize) {
+        let snapshot = self.elements.clone();
+        let mut split = Vec::new();
+
+        for (index, element) in self.elements.iter_mut().enumerate() {
+            if snapshot.iter().any(|other| element.is_enclosed_by(other)) {
+                element.faces = ElementFaces::default();
+                continue;
+            }
+
+            for direction in Direction::ALL {
+                if element.faces.get(direction).is_none() {
+                    continue;
+                }
+
+                let face_rect = PlaneRect::of_element(element, direction.axis());
+                let covers = snapshot
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_index, _)| *other_index != index)
+                    .filter_map(|(_, other)| element.face_cover(other, direction))
+                    .collect::<Vec<_>>();
+
+                if covers.is_empty() {
+                    continue;
+                }
+
+                let visible = face_rect.subtract(&covers);
+
+                if visible.is_empty() {
+                    element.faces.get_mut(direction).take();
+                } else if snapshot.len() + split.len() + visible.len() <= max_elements {
+                    let face = element.faces.get_mut(direction).take().unwrap();
+                    let bounds = element.auto_uv(direction);
+
+                    for rect in visible {
+                        let mut part = element.clone();
+                        rect.apply(&mut part, direction.axis());
+
+                        let mut face = face.clone();
+                        face.crop_uv(&bounds, &part.auto_uv(direction));
+
+                        part.faces = ElementFaces::default();
+                        *part.faces.get_mut(direction) = Some(face);
+                        split.push(part);
+                    }
+                }
+            }
+        }
+
+        self.elements
+            .retain(|element| (&element.faces).into_iter().any(|face| face.is_some()));
+        self.elements.extend(split);
+    }
+}
+
+impl ModelElement {
+    /// Whether both elements are axis-aligned or rotated by the exact same rotation.
+    fn shares_frame(&self, other: &Self) -> bool {
+        match (self.is_axis_aligned(), other.is_axis_aligned()) {
+            (true, true) => true,
+            (false, false) => self.rotation == other.rotation,
+            _ => false,
+        }
+    }
+
+    /// Whether a rotated element lies entirely inside an axis-aligned neighbour with every face.
+    fn is_enclosed_by(&self, other: &Self) -> bool {
+        let Some(rotation) = self.rotation.as_ref().filter(|_| !self.is_axis_aligned()) else {
+            return false;
+        };
+
+        if !other.is_axis_aligned() || (&other.faces).into_iter().any(|face| face.is_none()) {
+            return false;
+        }
+
+        let (from, to) = (self.from, self.to);
+        let corners = [from.x, to.x].into_iter().flat_map(|x| {
+            [from.y, to.y]
+                .into_iter()
+                .flat_map(move |y| [from.z, to.z].map(|z| Vec3::new(x, y, z)))
+        });
+
+        corners
+            .map(|corner| rotation.transform(corner))
+            .all(|corner| {
+                [Axis::X, Axis::Y, Axis::Z].iter().all(|axis| {
+                    let value = corner.axis(axis);
+                    value >= other.from.axis(axis) - ENCLOSED_EPSILON
+                        && value <= other.to.axis(axis) + ENCLOSED_EPSILON
+                })
+            })
+    }
+
+    /// The part of a face that is hidden by the opposite face of `other` on the same plane.
+    fn face_cover(&self, other: &Self, direction: Direction) -> Option<PlaneRect> {
+        let axis = direction.axis();
+        let opposite = direction.opposite();
+
+        if !self.shares_frame(other)
+            || other.faces.get(opposite).is_none()
+            || self.face_plane(direction) != other.face_plane(opposite)
+        {
+            return None;
+        }
+
+        PlaneRect::of_element(self, axis).intersection(&PlaneRect::of_element(other, axis))
+    }
+
+    fn face_plane(&self, direction: Direction) -> f32 {
+        if direction.is_positive() {
+            self.to.axis(&direction.axis())
+        } else {
+            self.from.axis(&direction.axis())
+        }
+    }
+}
+
+/// The two axes that span the plane perpendicular to `axis`.
+fn plane_axes(axis: Axis) -> (Axis, Axis) {
+    match axis {
+        Axis::X => (Axis::Y, Axis::Z),
+        Axis::Y => (Axis::X, Axis::Z),
+        Axis::Z => (Axis::X, Axis::Y),
+    }
+}
+
+/// A rectangle on the plane perpendicular to an axis, in the coordinates of `plane_axes`.
+#[derive(Debug, Clone, PartialEq)]
+struct PlaneRect {
+    min: (f32, f32),
+    max: (f32, f32),
+}
+
+impl PlaneRect {
+    fn of_element(element: &ModelElement, axis: Axis) -> Self {
+        let (u, v) = plane_axes(axis);
+
+        Self {
+            min: (element.from.axis(&u), element.from.axis(&v)),
+            max: (element.to.axis(&u), element.to.axis(&v)),
+        }
+    }
+
+    fn apply(&self, element: &mut ModelElement, axis: Axis) {
+        let (u, v) = plane_axes(axis);
+
+        *element.from.axis_mut(&u) = self.min.0;
+        *element.from.axis_mut(&v) = self.min.1;
+        *element.to.axis_mut(&u) = self.max.0;
+        *element.to.axis_mut(&v) = self.max.1;
+    }
+
+    fn intersection(&self, other: &Self) -> Option<Self> {
+        let min = (self.min.0.max(other.min.0), self.min.1.max(other.min.1));
+        let max = (self.max.0.min(other.max.0), self.max.1.min(other.max.1));
+
+        (min.0 < max.0 && min.1 < max.1).then_some(Self { min, max })
+    }
+
+    fn contains(&self, point: (f32, f32)) -> bool {
+        (self.min.0..self.max.0).contains(&point.0) && (self.min.1..self.max.1).contains(&point.1)
+    }
+
+    /// Whatever isn't covered by `holes`, as few rectangles as rows of a grid allow.
+    ///
+    /// The edges of every rectangle split the plane into a grid; visible cells are joined
+    /// into runs along each row, and runs spanning the same columns into taller rectangles.
+    fn subtract(&self, holes: &[Self]) -> Vec<Self> {
+        let grid = |min: f32, max: f32, edges: &dyn Fn(&Self) -> [f32; 2]| {
+            let mut lines = holes
+                .iter()
+                .flat_map(edges)
+                .filter(|line| *line > min && *line < max)
+                .chain([min, max])
+                .collect::<Vec<_>>();
+            lines.sort_by(f32::total_cmp);
+            lines.dedup();
+            lines
+        };
+
+        let columns = grid(self.min.0, self.max.0, &|hole| [hole.min.0, hole.max.0]);
+        let rows = grid(self.min.1, self.max.1, &|hole| [hole.min.1, hole.max.1]);
+
+        let mut rects: Vec<Self> = Vec::new();
+
+        for row in rows.windows(2) {
+            let mut run_start = None;
+
+            for (index, column) in columns.windows(2).enumerate() {
+                let center = ((column[0] + column[1]) / 2.0, (row[0] + row[1]) / 2.0);
+                let visible = !holes.iter().any(|hole| hole.contains(center));
+
+                match (visible, run_start) {
+                    (true, None) => run_start = Some(columns[index]),
+                    (false, Some(start)) => {
+                        push_run(&mut rects, (start, column[0]), (row[0], row[1]));
+                        run_start = None;
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(start) = run_start {
+                push_run(&mut rects, (start, self.max.0), (row[0], row[1]));
+            }
+        }
+
+        rects
+    }
+}
+
+/// Adds a run of visible cells, growing the rectangle right above it if it spans the same columns.
+fn push_run(rects: &mut Vec<PlaneRect>, columns: (f32, f32), row: (f32, f32)) {
+    let above = rects
+        .iter_mut()
+        .find(|rect| rect.min.0 == columns.0 && rect.max.0 == columns.1 && rect.max.1 == row.0);
+
+    match above {
+        Some(rect) => rect.max.1 = row.1,
+        None => rects.push(PlaneRect {
+            min: (columns.0, row.0),
+            max: (columns.1, row.1),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn split(elements: serde_json::Value, max_elements: usize) -> serde_json::Value {
+        let mut model = Model::from_elements(elements);
+        model.split_cull_faces(max_elements);
+        serde_json::to_value(model.elements).unwrap()
+    }
+
+    fn cull(elements: serde_json::Value) -> serde_json::Value {
+        let mut model = Model::from_elements(elements);
+        model.scan_filter_elements(|primary_element, scan_element| {
+            primary_element.cull_faces(scan_element)
+        });
+        serde_json::to_value(model.elements).unwrap()
+    }
+
+    #[test]
+    fn cull_same_rotation() {
+        let rotation = json!({ "origin": [8, 8, 8], "axis": "y", "angle": 22.5 });
+
+        assert_eq!(
+            cull(json!([
+                {
+                    "from": [4, 0, 4],
+                    "to": [12, 8, 12],
+                    "rotation": rotation,
+                    "faces": { "up": { "texture": "#side" }, "north": { "texture": "#side" } }
+                },
+                {
+                    "from": [4, 8, 4],
+                    "to": [12, 16, 12],
+                    "rotation": rotation,
+                    "faces": { "down": { "texture": "#side" }, "up": { "texture": "#side" } }
+                }
+            ])),
+            json!([
+                {
+                    "from": [4, 0, 4],
+                    "to": [12, 8, 12],
+                    "rotation": rotation,
+                    "faces": { "north": { "texture": "#side" } }
+                },
+                {
+                    "from": [4, 8, 4],
+                    "to": [12, 16, 12],
+                    "rotation": rotation,
+                    "faces": { "up": { "texture": "#side" } }
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn cull_different_rotation() {
+        let elements = json!([
+            {
+                "from": [4, 0, 4],
+                "to": [12, 8, 12],
+                "rotation": { "origin": [8, 8, 8], "axis": "y", "angle": 22.5 },
+                "faces": { "up": { "texture": "#side" } }
+            },
+            {
+                "from": [4, 8, 4],
+                "to": [12, 16, 12],
+                "rotation": { "origin": [8, 8, 8], "axis": "y", "angle": -22.5 },
+                "faces": { "down": { "texture": "#side" } }
+            }
+        ]);
+
+        assert_eq!(cull(elements.clone()), elements);
+    }
+
+    #[test]
+    fn cull_enclosed_rotated_element() {
+        let cube = json!({
+            "from": [0, 0, 0],
+            "to": [16, 16, 16],
+            "faces": {
+                "north": { "texture": "#side" },
+                "east": { "texture": "#side" },
+                "south": { "texture": "#side" },
+                "west": { "texture": "#side" },
+                "up": { "texture": "#side" },
+                "down": { "texture": "#side" }
+            }
+        });
+        let inner = |to: u8| {
+            json!({
+                "from": [4, 4, 4],
+                "to": [to, 12, 12],
+                "rotation": { "origin": [8, 8, 8], "axis": "z", "angle": 45, "rescale": true },
+                "faces": { "up": { "texture": "#side" } }
+            })
+        };
+
+        assert_eq!(cull(json!([cube, inner(12)])), json!([cube]));
+        // Rescaled, the corners of the longer element reach past the cube.
+        assert_eq!(cull(json!([cube, inner(14)])), json!([cube, inner(14)]));
+    }
+
+    #[test]
+    fn subtract_rects() {
+        let rect = PlaneRect {
+            min: (0.0, 0.0),
+            max: (16.0, 16.0),
+        };
+        let hole = PlaneRect {
+            min: (4.0, 4.0),
+            max: (12.0, 12.0),
+        };
+
+        assert_eq!(rect.subtract(std::slice::from_ref(&hole)).len(), 4);
+        assert!(hole.subtract(&[rect]).is_empty());
+    }
+
+    #[test]
+    fn split_half_covered_face() {
+        assert_eq!(
+            split(
+                json!([
+                    {
+                        "from": [0, 0, 0],
+                        "to": [16, 16, 8],
+                        "faces": {
+                            "south": { "texture": "#side", "uv": [0, 0, 16, 16] },
+                            "north": { "texture": "#side" }
+                        }
+                    },
+                    {
+                        "from": [0, 0, 8],
+                        "to": [8, 16, 16],
+                        "faces": { "north": { "texture": "#side" } }
+                    }
+                ]),
+                128
+            ),
+            json!([
+                {
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 8],
+                    "faces": { "north": { "texture": "#side" } }
+                },
+                {
+                    "from": [8, 0, 0],
+                    "to": [16, 16, 8],
+                    "faces": { "south": { "texture": "#side", "uv": [8, 0, 16, 16] } }
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn split_respects_max_elements() {
+        let elements = json!([
+            {
+                "from": [0, 0, 0],
+                "to": [16, 16, 8],
+                "faces": { "south": { "texture": "#side" } }
+            },
+            {
+                "from": [0, 0, 8],
+                "to": [8, 16, 16],
+                "faces": { "north": { "texture": "#side" }, "up": { "texture": "#side" } }
+            }
+        ]);
+
+        assert_eq!(
+            split(elements, 2),
+            json!([
+                {
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 8],
+                    "faces": { "south": { "texture": "#side" } }
+                },
+                {
+                    "from": [0, 0, 8],
+                    "to": [8, 16, 16],
+                    "faces": { "up": { "texture": "#side" } }
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn split_removes_covered_faces() {
+        assert_eq!(
+            split(
+                json!([
+                    {
+                        "from": [4, 0, 0],
+                        "to": [12, 8, 8],
+                        "faces": { "south": { "texture": "#side" } }
+                    },
+                    {
+                        "from": [4, 0, 8],
+                        "to": [12, 8, 16],
+                        "faces": { "north": { "texture": "#side" }, "up": { "texture": "#side" } }
+                    }
+                ]),
+                128
+            ),
+            json!([
+                {
+                    "from": [4, 0, 8],
+                    "to": [12, 8, 16],
+                    "faces": { "up": { "texture": "#side" } }
+                }
+            ])
+        );
+    }
+}
@@ -0,0 +1,66 @@
+use serde::Deserialize;
+
+use crate::{
+    asset::selector::AssetSelector,
+    compile::PackCompiler,
+    minecraft::asset::types::{identifier::Identifier, item::ItemModelDefinition},
+};
+
+use super::Modifier;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct HandAnimationModifier {
+    pub hand_animation_on_swap: bool,
+    #[serde(default)]
+    pub selector: AssetSelector,
+}
+
+impl Modifier<ItemModelDefinition, Identifier> for HandAnimationModifier {
+    fn apply_modifier(&self, asset: &mut ItemModelDefinition, _compiler: &mut PackCompiler) {
+        asset.hand_animation_on_swap = self.hand_animation_on_swap;
+    }
+
+    fn does_modifier_apply(&self, id: &Identifier) -> bool {
+        self.selector.applies(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        compile::library::tests::{bundle_compiler, write_bundles},
+        config::CollectionConfig,
+    };
+
+    use super::*;
+
+    #[test]
+    fn hand_animation_on_swap() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(directory.path(), &[]);
+        let mut compiler = bundle_compiler(&sanitizer, CollectionConfig::default());
+
+        let raw = json!({ "model": { "type": "model", "model": "item/compass" } });
+        let mut item = serde_json::from_value::<ItemModelDefinition>(raw.clone()).unwrap();
+
+        let modifier =
+            toml::from_str::<HandAnimationModifier>("hand_animation_on_swap = false").unwrap();
+        modifier.apply_modifier(&mut item, &mut compiler);
+
+        assert_eq!(
+            serde_json::to_value(&item).unwrap(),
+            json!({
+                "hand_animation_on_swap": false,
+                "model": { "type": "model", "model": "item/compass" }
+            })
+        );
+
+        let modifier =
+            toml::from_str::<HandAnimationModifier>("hand_animation_on_swap = true").unwrap();
+        modifier.apply_modifier(&mut item, &mut compiler);
+
+        assert_eq!(serde_json::to_value(&item).unwrap(), raw);
+    }
+}
@@ -0,0 +1,313 @@
+use serde::Deserialize;
+
+use crate::{
+    asset::selector::AssetSelector,
+    compile::PackCompiler,
+    minecraft::asset::{
+        model::{uv::TEXTURE_SIZE, Axis, ElementFace, ElementFaces, Model, ModelElement},
+        types::{identifier::Identifier, vec::Vec4},
+    },
+};
+
+use super::{zfighting::Direction, Modifier};
+
+/// UVs are extrapolated from one half of the merged element, which isn't exact for every size.
+const UV_EPSILON: f32 = 1e-4;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MergeModifier {
+    #[serde(default)]
+    selector: AssetSelector,
+}
+
+impl Modifier<Model, Identifier> for MergeModifier {
+    fn apply_modifier(&self, asset: &mut Model, _compiler: &mut PackCompiler) {
+        asset.merge_elements();
+    }
+
+    fn does_modifier_apply(&self, id: &Identifier) -> bool {
+        self.selector.applies(id)
+    }
+}
+
+impl Model {
+    /// Merges neighbouring elements into larger ones until no two elements can be merged.
+    fn merge_elements(&mut self) {
+        while let Some((index, other_index, merged)) = self.find_merge() {
+            self.elements[index] = merged;
+            self.elements.remove(other_index);
+        }
+    }
+
+    fn find_merge(&self) -> Option<(usize, usize, ModelElement)> {
+        self.elements
+            .iter()
+            .enumerate()
+            .flat_map(|(index, element)| {
+                self.elements
+                    .iter()
+                    .enumerate()
+                    .skip(index + 1)
+                    .map(move |(other_index, other)| (index, element, other_index, other))
+            })
+            .find_map(|(index, element, other_index, other)| {
+                element
+                    .merge(other)
+                    .map(|merged| (index, other_index, merged))
+            })
+    }
+}
+
+impl ModelElement {
+    /// The element covering both elements, if they line up and look the same as one element.
+    ///
+    /// Elements must be axis-aligned, touch along a whole side and have faces on the same sides.
+    /// Faces around the merged element must match and their UVs must continue from one element
+    /// into the other. Faces between the two elements are dropped, so elements with faces there
+    /// only merge when the faces on the four other sides enclose them.
+    fn merge(&self, other: &Self) -> Option<Self> {
+        if !self.is_axis_aligned() || !other.is_axis_aligned() || self.shade != other.shade {
+            return None;
+        }
+
+        let same_sides = Direction::ALL.iter().all(|direction| {
+            self.faces.get(*direction).is_some() == other.faces.get(*direction).is_some()
+        });
+
+        if !same_sides {
+            return None;
+        }
+
+        let axis = [Axis::X, Axis::Y, Axis::Z]
+            .into_iter()
+            .find(|axis| self.touches(other, *axis) || other.touches(self, *axis))?;
+        let (lower, upper) = if self.touches(other, axis) {
+            (self, other)
+        } else {
+            (other, self)
+        };
+
+        let between = lower.faces.get(Direction::positive(axis)).is_some()
+            || lower.faces.get(Direction::negative(axis)).is_some();
+        let enclosed = Direction::ALL
+            .iter()
+            .filter(|direction| direction.axis() != axis)
+            .all(|direction| lower.faces.get(*direction).is_some());
+
+        if between && !enclosed {
+            return None;
+        }
+
+        let mut merged = lower.clone();
+        *merged.to.axis_mut(&axis) = upper.to.axis(&axis);
+        merged.faces = ElementFaces::default();
+
+        for direction in Direction::ALL {
+            let face = if direction.axis() == axis {
+                let end = if direction.is_positive() {
+                    upper
+                } else {
+                    lower
+                };
+                end.faces.get(direction).cloned()
+            } else {
+                merged.merge_face(lower, upper, direction)?
+            };
+
+            *merged.faces.get_mut(direction) = face;
+        }
+
+        Some(merged)
+    }
+
+    /// Whether `other` starts where this element ends along `axis` and spans the same area.
+    fn touches(&self, other: &Self, axis: Axis) -> bool {
+        [Axis::X, Axis::Y, Axis::Z].iter().all(|each| {
+            if *each == axis {
+                self.to.axis(each) == other.from.axis(each)
+            } else {
+                self.from.axis(each) == other.from.axis(each)
+                    && self.to.axis(each) == other.to.axis(each)
+            }
+        })
+    }
+
+    /// The face of the merged element on a side that both elements share.
+    ///
+    /// `None` when the faces can't be merged; `Some(None)` when neither element has the face.
+    fn merge_face(
+        &self,
+        lower: &Self,
+        upper: &Self,
+        direction: Direction,
+    ) -> Option<Option<ElementFace>> {
+        let (lower_face, upper_face) =
+            match (lower.faces.get(direction), upper.faces.get(direction)) {
+                (Some(lower_face), Some(upper_face)) => (lower_face, upper_face),
+                _ => return Some(None),
+            };
+
+        if !lower_face.looks_like(upper_face) {
+            return None;
+        }
+
+        // Extending the lower face over the merged element must give the upper face its UV.
+        let mut merged_face = lower_face.clone();
+        merged_face.crop_uv(&lower.auto_uv(direction), &self.auto_uv(direction));
+
+        let mut expected_face = merged_face.clone();
+        expected_face.crop_uv(&self.auto_uv(direction), &upper.auto_uv(direction));
+
+        let upper_uv = upper_face
+            .uv
+            .clone()
+            .unwrap_or_else(|| upper.auto_uv(direction));
+        let expected_uv = expected_face
+            .uv
+            .clone()
+            .unwrap_or_else(|| upper.auto_uv(direction));
+
+        let in_texture = merged_face.uv.as_ref().is_none_or(Vec4::is_in_texture);
+
+        (in_texture && expected_uv.approx_eq(&upper_uv)).then_some(Some(merged_face))
+    }
+}
+
+impl ElementFace {
+    /// Whether the faces only differ in their UV.
+    fn looks_like(&self, other: &Self) -> bool {
+        self.texture == other.texture
+            && self.tint_index == other.tint_index
+            && self.rotation == other.rotation
+            && self.cullface == other.cullface
+    }
+}
+
+impl Direction {
+    fn positive(axis: Axis) -> Self {
+        match axis {
+            Axis::X => Self::East,
+            Axis::Y => Self::Up,
+            Axis::Z => Self::South,
+        }
+    }
+
+    fn negative(axis: Axis) -> Self {
+        Self::positive(axis).opposite()
+    }
+}
+
+impl Vec4 {
+    fn is_in_texture(&self) -> bool {
+        [self.w, self.x, self.y, self.z]
+            .iter()
+            .all(|value| (-UV_EPSILON..=TEXTURE_SIZE + UV_EPSILON).contains(value))
+    }
+
+    fn approx_eq(&self, other: &Self) -> bool {
+        [
+            self.w - other.w,
+            self.x - other.x,
+            self.y - other.y,
+            self.z - other.z,
+        ]
+        .iter()
+        .all(|difference| difference.abs() <= UV_EPSILON)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn merge(elements: serde_json::Value) -> serde_json::Value {
+        let mut model = Model::from_elements(elements);
+        model.merge_elements();
+        serde_json::to_value(model.elements).unwrap()
+    }
+
+    fn slab(from_y: u8, to_y: u8, side: serde_json::Value) -> serde_json::Value {
+        json!({
+            "from": [0, from_y, 0],
+            "to": [16, to_y, 16],
+            "faces": {
+                "north": side,
+                "south": side,
+                "east": side,
+                "west": side,
+                "up": { "texture": "#top" },
+                "down": { "texture": "#bottom" }
+            }
+        })
+    }
+
+    #[test]
+    fn merge_auto_uv() {
+        assert_eq!(
+            merge(json!([
+                slab(8, 16, json!({ "texture": "#side" })),
+                slab(0, 8, json!({ "texture": "#side" })),
+                slab(16, 24, json!({ "texture": "#side", "tintindex": 0 }))
+            ])),
+            json!([
+                slab(0, 16, json!({ "texture": "#side" })),
+                slab(16, 24, json!({ "texture": "#side", "tintindex": 0 }))
+            ])
+        );
+    }
+
+    #[test]
+    fn merge_contiguous_uv() {
+        assert_eq!(
+            merge(json!([
+                slab(0, 8, json!({ "texture": "#side", "uv": [0, 12, 16, 16] })),
+                slab(8, 16, json!({ "texture": "#side", "uv": [0, 8, 16, 12] }))
+            ])),
+            json!([slab(
+                0,
+                16,
+                json!({ "texture": "#side", "uv": [0, 8, 16, 16] })
+            )])
+        );
+    }
+
+    #[test]
+    fn merge_rejects_broken_uv() {
+        let elements = json!([
+            slab(0, 8, json!({ "texture": "#side", "uv": [0, 0, 16, 8] })),
+            slab(8, 16, json!({ "texture": "#side", "uv": [0, 0, 16, 8] }))
+        ]);
+
+        assert_eq!(merge(elements.clone()), elements);
+    }
+
+    #[test]
+    fn merge_rejects_open_sides() {
+        let layer = |from_y: u8, to_y: u8| {
+            json!({
+                "from": [0, from_y, 0],
+                "to": [16, to_y, 16],
+                "faces": { "up": { "texture": "#top" }, "down": { "texture": "#top" } }
+            })
+        };
+        let elements = json!([layer(0, 8), layer(8, 16)]);
+
+        assert_eq!(merge(elements.clone()), elements);
+    }
+
+    #[test]
+    fn merge_rejects_mismatched_sides() {
+        let elements = json!([
+            slab(0, 8, json!({ "texture": "#side" })),
+            {
+                "from": [0, 8, 0],
+                "to": [16, 16, 16],
+                "faces": { "north": { "texture": "#side" } }
+            }
+        ]);
+
+        assert_eq!(merge(elements.clone()), elements);
+    }
+}
@@ -1,11 +1,19 @@
+use std::path::PathBuf;
+
+use regex::Regex;
 use serde::Deserialize;
+use serde_with::{serde_as, DisplayFromStr};
 
 use crate::{
     asset::selector::AssetSelector,
     compile::PackCompiler,
     minecraft::asset::{
+        blockstate::Blockstate,
         model::{IdentifierOrVariable, Model},
-        types::identifier::{AssetType, Identifier},
+        types::{
+            identifier::{AssetType, Identifier},
+            item::ItemModelDefinition,
+        },
     },
 };
 
@@ -21,31 +29,136 @@ pub struct Redirect {
     pub selector: AssetSelector,
 }
 
-impl Modifier<Model, Identifier> for Redirect {
-    fn apply_modifier(&self, asset: &mut Model, _compiler: &mut PackCompiler) {
-        for texture in asset.textures.values_mut() {
-            match texture {
-                IdentifierOrVariable::Variable(_) => (),
-                IdentifierOrVariable::Identifier(id) => id.apply_redirect(self),
+impl Redirect {
+    /// Textures are redirected within the texture map; models within the parent and overrides.
+    fn redirect_model(&self, model: &mut Model) {
+        match self.asset_type {
+            AssetType::Texture => {
+                for texture in model.textures.values_mut() {
+                    match texture {
+                        IdentifierOrVariable::Variable(_) => (),
+                        IdentifierOrVariable::Identifier(id) => id.apply_redirect(self),
+                    }
+                }
+            }
+            AssetType::Model => {
+                if let Some(parent) = &mut model.parent {
+                    parent.apply_redirect(self);
+                }
+
+                for item_override in &mut model.overrides {
+                    item_override.model.apply_redirect(self);
+                }
             }
+            _ => (),
         }
     }
 
+    fn redirect_blockstate(&self, blockstate: &mut Blockstate) {
+        if self.asset_type != AssetType::Model {
+            return;
+        }
+
+        for state in blockstate.states_mut() {
+            for model in state.models_mut() {
+                model.apply_redirect(self);
+            }
+        }
+    }
+
+    fn redirect_item(&self, item: &mut ItemModelDefinition) {
+        if self.asset_type != AssetType::Model {
+            return;
+        }
+
+        for model in item.model.models_mut() {
+            model.apply_redirect(self);
+        }
+    }
+}
+
+impl Modifier<Model, Identifier> for Redirect {
+    fn apply_modifier(&self, asset: &mut Model, _compiler: &mut PackCompiler) {
+        self.redirect_model(asset);
+    }
+
+    fn does_modifier_apply(&self, id: &Identifier) -> bool {
+        self.selector.applies(id)
+    }
+}
+
+impl Modifier<Blockstate, Identifier> for Redirect {
+    fn apply_modifier(&self, asset: &mut Blockstate, _compiler: &mut PackCompiler) {
+        self.redirect_blockstate(asset);
+    }
+
+    fn does_modifier_apply(&self, id: &Identifier) -> bool {
+        self.selector.applies(id)
+    }
+}
+
+impl Modifier<ItemModelDefinition, Identifier> for Redirect {
+    fn apply_modifier(&self, asset: &mut ItemModelDefinition, _compiler: &mut PackCompiler) {
+        self.redirect_item(asset);
+    }
+
     fn does_modifier_apply(&self, id: &Identifier) -> bool {
         self.selector.applies(id)
     }
 }
 
+#[serde_as]
 #[derive(Debug, Deserialize, Clone)]
 #[serde(rename_all = "snake_case")]
 pub enum RedirectAffect {
+    /// Replaces the path prefix `from` with `to`.
     Path,
+    /// Replaces the identifier `from` with `to`.
+    Exact,
+    /// Moves every identifier in the namespace of `from` to the namespace of `to`.
+    Namespace,
+    /// Rewrites matching paths within the namespace of `from`.
+    ///
+    /// The path of `to` is the replacement, where `${1}` inserts the first capture group.
+    Regex {
+        #[serde_as(as = "DisplayFromStr")]
+        pattern: Regex,
+    },
 }
 
 impl Identifier {
     pub fn apply_redirect(&mut self, redirect: &Redirect) {
-        match redirect.affect {
+        match &redirect.affect {
             RedirectAffect::Path => self.apply_redirect_path(redirect),
+            RedirectAffect::Exact => self.apply_redirect_exact(redirect),
+            RedirectAffect::Namespace => self.apply_redirect_namespace(redirect),
+            RedirectAffect::Regex { pattern } => self.apply_redirect_regex(redirect, pattern),
+        }
+    }
+
+    fn apply_redirect_exact(&mut self, redirect: &Redirect) {
+        if *self == redirect.from {
+            self.clone_from(&redirect.to);
+        }
+    }
+
+    fn apply_redirect_namespace(&mut self, redirect: &Redirect) {
+        if self.namespace == redirect.from.namespace {
+            self.namespace.clone_from(&redirect.to.namespace);
+        }
+    }
+
+    fn apply_redirect_regex(&mut self, redirect: &Redirect, pattern: &Regex) {
+        if self.namespace != redirect.from.namespace {
+            return;
+        }
+
+        let path = self.path.to_string_lossy().into_owned();
+
+        if pattern.is_match(&path) {
+            let replacement = redirect.to.path.to_string_lossy();
+            self.path = PathBuf::from(pattern.replace(&path, replacement.as_ref()).as_ref());
+            self.namespace.clone_from(&redirect.to.namespace);
         }
     }
 
@@ -63,8 +176,20 @@ impl Identifier {
 
 #[cfg(test)]
 mod tests {
+    use serde_json::json;
+
     use super::*;
 
+    fn redirect(asset_type: AssetType) -> Redirect {
+        Redirect {
+            affect: RedirectAffect::Path,
+            asset_type,
+            from: Identifier::minecraft("blocks"),
+            to: Identifier::minecraft("block"),
+            selector: Default::default(),
+        }
+    }
+
     #[test]
     fn redirect_path() {
         let redirect = Redirect {
@@ -112,4 +237,190 @@ mod tests {
 
         assert_eq!(id, expected);
     }
+
+    #[test]
+    fn redirect_exact() {
+        let redirect = Redirect {
+            affect: RedirectAffect::Exact,
+            asset_type: AssetType::Texture,
+            from: Identifier::minecraft("block/grass_path_top"),
+            to: Identifier::minecraft("block/dirt_path_top"),
+            selector: Default::default(),
+        };
+        let mut id = Identifier::minecraft("block/grass_path_top");
+        id.apply_redirect(&redirect);
+        let expected = Identifier::minecraft("block/dirt_path_top");
+
+        assert_eq!(id, expected);
+    }
+
+    #[test]
+    fn redirect_exact_unaffected() {
+        let redirect = Redirect {
+            affect: RedirectAffect::Exact,
+            asset_type: AssetType::Texture,
+            from: Identifier::minecraft("block/grass_path"),
+            to: Identifier::minecraft("block/dirt_path"),
+            selector: Default::default(),
+        };
+        let mut id = Identifier::minecraft("block/grass_path_top");
+        let expected = id.clone();
+        id.apply_redirect(&redirect);
+
+        assert_eq!(id, expected);
+    }
+
+    #[test]
+    fn redirect_namespace() {
+        let redirect = Redirect {
+            affect: RedirectAffect::Namespace,
+            asset_type: AssetType::Texture,
+            from: Identifier::new("quark", ""),
+            to: Identifier::new("quark_legacy", ""),
+            selector: Default::default(),
+        };
+        let mut id = Identifier::new("quark", "block/glass");
+        id.apply_redirect(&redirect);
+        let expected = Identifier::new("quark_legacy", "block/glass");
+
+        assert_eq!(id, expected);
+
+        let mut id = Identifier::minecraft("block/glass");
+        let expected = id.clone();
+        id.apply_redirect(&redirect);
+
+        assert_eq!(id, expected);
+    }
+
+    #[test]
+    fn redirect_regex() {
+        let redirect = Redirect {
+            affect: RedirectAffect::Regex {
+                pattern: Regex::new("^blocks/log_(.+)$").unwrap(),
+            },
+            asset_type: AssetType::Texture,
+            from: Identifier::minecraft(""),
+            to: Identifier::minecraft("block/${1}_log"),
+            selector: Default::default(),
+        };
+        let mut id = Identifier::minecraft("blocks/log_spruce");
+        id.apply_redirect(&redirect);
+        let expected = Identifier::minecraft("block/spruce_log");
+
+        assert_eq!(id, expected);
+    }
+
+    #[test]
+    fn redirect_regex_unaffected() {
+        let redirect = Redirect {
+            affect: RedirectAffect::Regex {
+                pattern: Regex::new("^blocks/log_(.+)$").unwrap(),
+            },
+            asset_type: AssetType::Texture,
+            from: Identifier::minecraft(""),
+            to: Identifier::minecraft("block/${1}_log"),
+            selector: Default::default(),
+        };
+        let mut id = Identifier::minecraft("blocks/planks_spruce");
+        let expected = id.clone();
+        id.apply_redirect(&redirect);
+
+        assert_eq!(id, expected);
+    }
+
+    #[test]
+    fn redirect_regex_from_toml() {
+        let redirect = toml::from_str::<Redirect>(
+            r#"
+            affect = { regex = { pattern = "^blocks/(.+)$" } }
+            asset_type = "texture"
+            from = "minecraft:"
+            to = "quark:block/${1}"
+            "#,
+        )
+        .unwrap();
+        let mut id = Identifier::minecraft("blocks/dirt");
+        id.apply_redirect(&redirect);
+        let expected = Identifier::new("quark", "block/dirt");
+
+        assert_eq!(id, expected);
+    }
+
+    #[test]
+    fn redirect_model_asset_type() {
+        let raw = json!({
+            "parent": "blocks/cube_all",
+            "textures": { "all": "blocks/dirt" },
+            "overrides": [{ "predicate": { "angle": 1 }, "model": "blocks/dirt_angled" }]
+        });
+
+        let mut model = serde_json::from_value::<Model>(raw.clone()).unwrap();
+        redirect(AssetType::Model).redirect_model(&mut model);
+
+        assert_eq!(
+            serde_json::to_value(&model).unwrap(),
+            json!({
+                "parent": "block/cube_all",
+                "textures": { "all": "blocks/dirt" },
+                "overrides": [{ "predicate": { "angle": 1 }, "model": "block/dirt_angled" }]
+            })
+        );
+
+        let mut model = serde_json::from_value::<Model>(raw).unwrap();
+        redirect(AssetType::Texture).redirect_model(&mut model);
+
+        assert_eq!(
+            serde_json::to_value(&model).unwrap(),
+            json!({
+                "parent": "blocks/cube_all",
+                "textures": { "all": "block/dirt" },
+                "overrides": [{ "predicate": { "angle": 1 }, "model": "blocks/dirt_angled" }]
+            })
+        );
+    }
+
+    #[test]
+    fn redirect_blockstate_models() {
+        let raw = json!({ "variants": { "": { "model": "blocks/dirt" } } });
+
+        let mut blockstate = serde_json::from_value::<Blockstate>(raw.clone()).unwrap();
+        redirect(AssetType::Texture).redirect_blockstate(&mut blockstate);
+        assert_eq!(serde_json::to_value(&blockstate).unwrap(), raw);
+
+        redirect(AssetType::Model).redirect_blockstate(&mut blockstate);
+        assert_eq!(
+            serde_json::to_value(&blockstate).unwrap(),
+            json!({ "variants": { "": { "model": "block/dirt" } } })
+        );
+    }
+
+    #[test]
+    fn redirect_item_models() {
+        let raw = json!({
+            "model": {
+                "type": "condition",
+                "property": "using_item",
+                "on_true": { "type": "model", "model": "blocks/dirt_using" },
+                "on_false": { "type": "model", "model": "blocks/dirt" }
+            }
+        });
+
+        let mut item = serde_json::from_value::<ItemModelDefinition>(raw.clone()).unwrap();
+        redirect(AssetType::Texture).redirect_item(&mut item);
+        assert_eq!(serde_json::to_value(&item).unwrap(), raw);
+
+        redirect(AssetType::Model).redirect_item(&mut item);
+
+        assert_eq!(
+            serde_json::to_value(&item).unwrap(),
+            json!({
+                "model": {
+                    "type": "condition",
+                    "property": "using_item",
+                    "on_true": { "type": "model", "model": "block/dirt_using" },
+                    "on_false": { "type": "model", "model": "block/dirt" }
+                }
+            })
+        );
+    }
 }
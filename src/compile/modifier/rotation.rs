@@ -0,0 +1,123 @@
+use serde::Deserialize;
+
+use crate::{
+    asset::selector::AssetSelector,
+    compile::PackCompiler,
+    minecraft::asset::{
+        blockstate::{Blockstate, ModelState, WeightedState},
+        types::{identifier::Identifier, rotation::StateRotation},
+    },
+};
+
+use super::Modifier;
+
+/// Adds a weighted copy of every model state for each rotation around the y axis,
+/// so the game picks one at random per block.
+#[derive(Debug, Deserialize, Clone)]
+pub struct RandomYRotationModifier {
+    #[serde(default = "RandomYRotationModifier::default_rotations")]
+    pub rotations: Vec<StateRotation>,
+    #[serde(default)]
+    pub selector: AssetSelector,
+}
+
+impl RandomYRotationModifier {
+    fn default_rotations() -> Vec<StateRotation> {
+        (0..4).map(StateRotation::from_quarters).collect()
+    }
+
+    fn rotate_state(&self, state: &mut ModelState) {
+        let states = std::mem::replace(state, ModelState::Weighted(Vec::new())).into_weighted();
+
+        let rotated = states
+            .iter()
+            .flat_map(|state| {
+                self.rotations.iter().map(|rotation| WeightedState {
+                    y: state.y.clone() + rotation.clone(),
+                    ..state.clone()
+                })
+            })
+            .collect();
+
+        *state = ModelState::Weighted(rotated);
+    }
+}
+
+impl Modifier<Blockstate, Identifier> for RandomYRotationModifier {
+    fn apply_modifier(&self, asset: &mut Blockstate, _compiler: &mut PackCompiler) {
+        for state in asset.states_mut() {
+            self.rotate_state(state);
+        }
+    }
+
+    fn does_modifier_apply(&self, id: &Identifier) -> bool {
+        self.selector.applies(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        compile::library::tests::{bundle_compiler, write_bundles},
+        config::CollectionConfig,
+    };
+
+    use super::*;
+
+    #[test]
+    fn random_y_rotation() {
+        let modifier = RandomYRotationModifier {
+            rotations: vec![StateRotation::Degrees0, StateRotation::Degrees180],
+            selector: Default::default(),
+        };
+
+        let mut state = serde_json::from_value::<ModelState>(json!([
+            { "model": "block/stone", "y": 90 },
+            { "model": "block/stone_mirrored", "weight": 2 }
+        ]))
+        .unwrap();
+
+        modifier.rotate_state(&mut state);
+
+        assert_eq!(
+            serde_json::to_value(&state).unwrap(),
+            json!([
+                { "model": "block/stone", "y": 90 },
+                { "model": "block/stone", "y": 270 },
+                { "model": "block/stone_mirrored", "weight": 2 },
+                { "model": "block/stone_mirrored", "y": 180, "weight": 2 }
+            ])
+        );
+    }
+
+    #[test]
+    fn random_y_rotation_blockstate() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(directory.path(), &[]);
+        let mut compiler = bundle_compiler(&sanitizer, CollectionConfig::default());
+
+        let modifier = toml::from_str::<RandomYRotationModifier>("").unwrap();
+        let mut blockstate = serde_json::from_value::<Blockstate>(json!({
+            "multipart": [{ "apply": { "model": "block/stone" } }]
+        }))
+        .unwrap();
+
+        modifier.apply_modifier(&mut blockstate, &mut compiler);
+
+        assert_eq!(
+            serde_json::to_value(&blockstate).unwrap(),
+            json!({
+                "multipart": [{
+                    "apply": [
+                        { "model": "block/stone" },
+                        { "model": "block/stone", "y": 90 },
+                        { "model": "block/stone", "y": 180 },
+                        { "model": "block/stone", "y": 270 }
+                    ]
+                }]
+            })
+        );
+    }
+}
@@ -0,0 +1,91 @@
+use serde::Deserialize;
+
+use crate::{
+    asset::selector::AssetSelector,
+    compile::PackCompiler,
+    minecraft::asset::{blockstate::Blockstate, types::identifier::Identifier},
+};
+
+use super::Modifier;
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct UvlockModifier {
+    #[serde(default = "UvlockModifier::default_uvlock")]
+    pub uvlock: bool,
+    #[serde(default)]
+    pub selector: AssetSelector,
+}
+
+impl UvlockModifier {
+    fn default_uvlock() -> bool {
+        true
+    }
+}
+
+impl Modifier<Blockstate, Identifier> for UvlockModifier {
+    fn apply_modifier(&self, asset: &mut Blockstate, _compiler: &mut PackCompiler) {
+        for state in asset.states_mut() {
+            state.set_uvlock(self.uvlock);
+        }
+    }
+
+    fn does_modifier_apply(&self, id: &Identifier) -> bool {
+        self.selector.applies(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        compile::library::tests::{bundle_compiler, write_bundles},
+        config::CollectionConfig,
+    };
+
+    use super::*;
+
+    #[test]
+    fn uvlock_every_state() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(directory.path(), &[]);
+        let mut compiler = bundle_compiler(&sanitizer, CollectionConfig::default());
+
+        let modifier = toml::from_str::<UvlockModifier>("").unwrap();
+        let mut blockstate = serde_json::from_value::<Blockstate>(json!({
+            "variants": {
+                "axis=x": { "model": "block/log", "x": 90 },
+                "axis=y": [{ "model": "block/log" }, { "model": "block/log_mirrored" }]
+            }
+        }))
+        .unwrap();
+
+        modifier.apply_modifier(&mut blockstate, &mut compiler);
+
+        assert_eq!(
+            serde_json::to_value(&blockstate).unwrap(),
+            json!({
+                "variants": {
+                    "axis=x": { "model": "block/log", "x": 90, "uvlock": true },
+                    "axis=y": [
+                        { "model": "block/log", "uvlock": true },
+                        { "model": "block/log_mirrored", "uvlock": true }
+                    ]
+                }
+            })
+        );
+
+        let modifier = toml::from_str::<UvlockModifier>("uvlock = false").unwrap();
+        modifier.apply_modifier(&mut blockstate, &mut compiler);
+
+        assert_eq!(
+            serde_json::to_value(&blockstate).unwrap(),
+            json!({
+                "variants": {
+                    "axis=x": { "model": "block/log", "x": 90 },
+                    "axis=y": [{ "model": "block/log" }, { "model": "block/log_mirrored" }]
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use serde::Deserialize;
+
+use crate::{
+    asset::selector::AssetSelector,
+    compile::PackCompiler,
+    minecraft::asset::types::{
+        identifier::Identifier,
+        item::{ConditionProperty, ItemModelDefinition, ModelType, SelectProperty},
+    },
+};
+
+use super::Modifier;
+
+/// Places every selected item definition within a new `condition` or `select` tree.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ItemWrapModifier {
+    #[serde(flatten)]
+    pub wrap: ItemWrap,
+    #[serde(default)]
+    pub selector: AssetSelector,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "wrap", rename_all = "snake_case")]
+pub enum ItemWrap {
+    /// The original model is used when the condition is false.
+    Condition {
+        #[serde(flatten)]
+        property: ConditionProperty,
+        on_true: ModelType,
+    },
+    /// The original model is used when no case matches.
+    Select {
+        #[serde(flatten)]
+        property: SelectProperty,
+    },
+}
+
+impl ItemWrap {
+    fn wrap(&self, model: ModelType) -> ModelType {
+        match self.clone() {
+            Self::Condition { property, on_true } => ModelType::Condition {
+                property,
+                on_true: Box::new(on_true),
+                on_false: Box::new(model),
+            },
+            Self::Select { property } => ModelType::Select {
+                property,
+                fallback: Box::new(model),
+            },
+        }
+    }
+}
+
+impl Modifier<ItemModelDefinition, Identifier> for ItemWrapModifier {
+    fn apply_modifier(&self, asset: &mut ItemModelDefinition, _compiler: &mut PackCompiler) {
+        let model = std::mem::replace(&mut asset.model, ModelType::Empty);
+        asset.model = self.wrap.wrap(model);
+    }
+
+    fn does_modifier_apply(&self, id: &Identifier) -> bool {
+        self.selector.applies(id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        asset::LoadableAsset,
+        compile::modifier::configurable::{ConfigurableModifier, ConfigurableModifierCollection},
+    };
+
+    fn wrap(modifier: &str) -> serde_json::Value {
+        let collection = ConfigurableModifierCollection::load_asset(modifier).unwrap();
+        let Some(ConfigurableModifier::ItemWrap(modifier)) = collection.modifiers.first() else {
+            panic!("Expected an item wrap modifier");
+        };
+
+        let model = serde_json::from_value(json!({ "type": "model", "model": "item/stick" }));
+        serde_json::to_value(modifier.wrap.wrap(model.unwrap())).unwrap()
+    }
+
+    #[test]
+    fn wrap_condition() {
+        let wrapped = wrap(
+            r#"
+            [[modifiers]]
+            type = "item_wrap"
+            wrap = "condition"
+            property = "using_item"
+            on_true = { type = "model", model = "item/stick_using" }
+            "#,
+        );
+
+        assert_eq!(
+            wrapped,
+            json!({
+                "type": "condition",
+                "property": "using_item",
+                "on_true": { "type": "model", "model": "item/stick_using" },
+                "on_false": { "type": "model", "model": "item/stick" }
+            })
+        );
+    }
+
+    #[test]
+    fn wrap_select() {
+        let wrapped = wrap(
+            r#"
+            [[modifiers]]
+            type = "item_wrap"
+            wrap = "select"
+            property = "display_context"
+            cases = [{ when = "gui", model = { type = "model", model = "item/stick_gui" } }]
+            "#,
+        );
+
+        assert_eq!(
+            wrapped,
+            json!({
+                "type": "select",
+                "property": "display_context",
+                "cases": [{ "when": "gui", "model": { "type": "model", "model": "item/stick_gui" } }],
+                "fallback": { "type": "model", "model": "item/stick" }
+            })
+        );
+    }
+}
@@ -5,7 +5,7 @@ use crate::{
     asset::selector::AssetSelector,
     compile::{modifier::Modifier, PackCompiler},
     minecraft::asset::{
-        model::{FaceNormal, Model, ModelElement},
+        model::{Axis, FaceNormal, Model, ModelElement},
         types::{identifier::Identifier, vec::Vec3},
     },
 };
@@ -21,6 +21,41 @@ pub enum Direction {
     Down,
 }
 
+impl Direction {
+    pub const ALL: [Self; 6] = [
+        Self::North,
+        Self::East,
+        Self::South,
+        Self::West,
+        Self::Up,
+        Self::Down,
+    ];
+
+    pub fn opposite(&self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::East => Self::West,
+            Self::South => Self::North,
+            Self::West => Self::East,
+            Self::Up => Self::Down,
+            Self::Down => Self::Up,
+        }
+    }
+
+    /// The axis that faces pointing this way are perpendicular to.
+    pub fn axis(&self) -> Axis {
+        match self {
+            Self::North | Self::South => Axis::Z,
+            Self::East | Self::West => Axis::X,
+            Self::Up | Self::Down => Axis::Y,
+        }
+    }
+
+    pub fn is_positive(&self) -> bool {
+        matches!(self, Self::East | Self::South | Self::Up)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct ZFightingModifier {
     pub direction: Direction,
@@ -0,0 +1,190 @@
+use std::path::PathBuf;
+
+use anyhow::Context;
+use oxipng::{Options, StripChunks};
+use sha2::{Digest, Sha256};
+
+use super::PackCompiler;
+
+/// Changes whenever optimized PNGs would come out differently for the same options.
+const PNG_CACHE_VERSION: u32 = 1;
+
+impl PackCompiler<'_> {
+    /// Re-encodes a PNG losslessly when the profile allows it.
+    ///
+    /// Results are cached by the hash of the original image and the optimization options,
+    /// so unchanged textures are only optimized once across builds.
+    pub async fn optimize_png(&self, raw: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+        if !self.profile.optimize_png {
+            return Ok(raw);
+        }
+
+        let cache_path = self
+            .png_cache_path()?
+            .join(format!("{:x}", png_cache_key(&raw)))
+            .with_extension("png");
+
+        self.png_cache_used
+            .lock()
+            .expect("PNG cache lock poisoned")
+            .insert(cache_path.clone());
+
+        if let Ok(cached) = async_fs::read(&cache_path).await {
+            return Ok(cached);
+        }
+
+        let optimized = tokio::task::spawn_blocking(move || optimize(&raw)).await??;
+
+        if let Some(cache_folder) = cache_path.parent() {
+            async_fs::create_dir_all(cache_folder).await?;
+        }
+        async_fs::write(&cache_path, &optimized)
+            .await
+            .with_context(|| format!("Failed to cache PNG: {}", cache_path.display()))?;
+
+        Ok(optimized)
+    }
+
+    /// Removes cached PNGs this build didn't use.
+    ///
+    /// Nothing is removed when PNGs aren't optimized, so the cache survives switching profiles.
+    pub async fn prune_png_cache(&self) -> anyhow::Result<()> {
+        if !self.profile.optimize_png {
+            return Ok(());
+        }
+
+        let cache_folder = self.png_cache_path()?;
+        let Ok(entries) = std::fs::read_dir(&cache_folder) else {
+            return Ok(());
+        };
+
+        let used =
+            std::mem::take(&mut *self.png_cache_used.lock().expect("PNG cache lock poisoned"));
+
+        for entry in entries {
+            let path = entry?.path();
+
+            if !used.contains(&path) {
+                async_fs::remove_file(&path)
+                    .await
+                    .with_context(|| format!("Failed to prune cached PNG: {}", path.display()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Every pack has its own cache, so pruning one build leaves the others alone.
+    fn png_cache_path(&self) -> anyhow::Result<PathBuf> {
+        let pack_name = self.compile_path.file_name().unwrap_or_default();
+
+        self.project_sanitizer.join(
+            PathBuf::from("build")
+                .join(".cache")
+                .join("png")
+                .join(pack_name),
+        )
+    }
+}
+
+fn options() -> Options {
+    Options {
+        strip: StripChunks::Safe,
+        ..Options::max_compression()
+    }
+}
+
+fn png_cache_key(raw: &[u8]) -> impl std::fmt::LowerHex {
+    Sha256::new()
+        .chain_update(PNG_CACHE_VERSION.to_le_bytes())
+        .chain_update(format!("{:?}", options()))
+        .chain_update(raw)
+        .finalize()
+}
+
+fn optimize(raw: &[u8]) -> anyhow::Result<Vec<u8>> {
+    oxipng::optimize_from_memory(raw, &options()).with_context(|| "Failed to optimize PNG")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use std::sync::Arc;
+
+    use image::{ImageFormat, Rgba, RgbaImage};
+
+    use crate::{
+        compile::{library::tests::write_bundles, tracking::AssetTracker},
+        config::{PackMetaConfig, ProfileConfig},
+    };
+
+    use super::*;
+
+    fn png(texture: &RgbaImage) -> Vec<u8> {
+        let mut raw = Cursor::new(Vec::new());
+        texture.write_to(&mut raw, ImageFormat::Png).unwrap();
+        raw.into_inner()
+    }
+
+    #[test]
+    fn optimize_lossless() {
+        let texture = RgbaImage::from_fn(16, 16, |x, y| {
+            if (x + y) % 2 == 0 {
+                Rgba([255, 0, 0, 255])
+            } else {
+                Rgba([0, 0, 255, 128])
+            }
+        });
+
+        let raw = png(&texture);
+
+        let optimized = optimize(&raw).unwrap();
+        let decoded = image::load_from_memory(&optimized).unwrap().into_rgba8();
+
+        assert!(optimized.len() <= raw.len());
+        assert_eq!(decoded, texture);
+    }
+
+    #[tokio::test]
+    async fn prune_unused_cache() {
+        let directory = tempfile::tempdir().unwrap();
+        let sanitizer = write_bundles(directory.path(), &[]);
+        let profile = ProfileConfig {
+            optimize_png: true,
+            ..Default::default()
+        };
+        let pack = PackMetaConfig {
+            name: Some("test".into()),
+            ..Default::default()
+        };
+        let compiler = PackCompiler::new(
+            &sanitizer,
+            directory.path().join("minecraft"),
+            pack,
+            Arc::new(profile),
+            Default::default(),
+            Arc::new(AssetTracker::default()),
+            None,
+        )
+        .unwrap();
+
+        let cache_folder = compiler.png_cache_path().unwrap();
+        std::fs::create_dir_all(&cache_folder).unwrap();
+        std::fs::write(cache_folder.join("stale.png"), []).unwrap();
+
+        compiler
+            .optimize_png(png(&RgbaImage::new(2, 2)))
+            .await
+            .unwrap();
+        compiler.prune_png_cache().await.unwrap();
+
+        let cached = std::fs::read_dir(&cache_folder)
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .collect::<Vec<_>>();
+
+        assert_eq!(cached.len(), 1);
+        assert_ne!(cached[0], "stale.png");
+    }
+}
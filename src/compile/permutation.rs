@@ -0,0 +1,231 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use anyhow::Context;
+use image::RgbaImage;
+use indexmap::IndexMap;
+
+use crate::{
+    asset::recipe::{palette_mapping, permute},
+    minecraft::asset::{atlas::AtlasSource, types::identifier::Identifier},
+};
+
+use super::{
+    library::CompiledAssetLibrary,
+    vanilla::{self, VanillaAssets},
+    PackCompiler,
+};
+
+const DEFAULT_SEPARATOR: &str = "_";
+
+impl PackCompiler<'_> {
+    pub fn process_permutations(&self, library: &mut CompiledAssetLibrary) -> anyhow::Result<()> {
+        if self.emulate_paletted_permutations {
+            library.emulate_paletted_permutations(self.vanilla.as_deref())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl CompiledAssetLibrary {
+    /// Generates every permuted texture, replacing each `paletted_permutations` source
+    /// with `single` sources for formats that don't support it.
+    ///
+    /// Textures that aren't in the pack are read from the vanilla assets.
+    fn emulate_paletted_permutations(
+        &mut self,
+        vanilla: Option<&VanillaAssets>,
+    ) -> anyhow::Result<()> {
+        for (atlas_id, atlas) in &mut self.atlases {
+            let mut sources = Vec::with_capacity(atlas.sources.len());
+
+            for source in std::mem::take(&mut atlas.sources) {
+                let AtlasSource::PalettedPermutations {
+                    textures,
+                    palette_key,
+                    permutations,
+                    separator,
+                } = source
+                else {
+                    sources.push(source);
+                    continue;
+                };
+
+                let generated = generate_permutations(
+                    &textures,
+                    &palette_key,
+                    &permutations,
+                    separator.as_deref().unwrap_or(DEFAULT_SEPARATOR),
+                    &self.textures,
+                    &self.generated_textures,
+                    vanilla,
+                )
+                .with_context(|| {
+                    format!("Failed to emulate permutations in atlas: {}", atlas_id)
+                })?;
+
+                for (id, texture) in generated {
+                    sources.push(AtlasSource::Single {
+                        resource: id.clone(),
+                        sprite: None,
+                    });
+                    self.generated_textures.insert(id, texture);
+                }
+            }
+
+            atlas.sources = sources;
+        }
+
+        Ok(())
+    }
+}
+
+fn generate_permutations(
+    textures: &[Identifier],
+    palette_key: &Identifier,
+    permutations: &IndexMap<String, Identifier>,
+    separator: &str,
+    texture_files: &HashMap<Identifier, PathBuf>,
+    generated_textures: &HashMap<Identifier, RgbaImage>,
+    vanilla: Option<&VanillaAssets>,
+) -> anyhow::Result<Vec<(Identifier, RgbaImage)>> {
+    let load = |id: &Identifier| load_texture(id, texture_files, generated_textures, vanilla);
+    let palette_key_texture = load(palette_key)?;
+    let mut generated = Vec::with_capacity(textures.len() * permutations.len());
+
+    for (permutation_name, palette) in permutations {
+        let mapping = palette_mapping(&palette_key_texture, &load(palette)?)
+            .with_context(|| format!("Palette doesn't match its key: {}", palette))?;
+
+        for texture_id in textures {
+            let texture = load(texture_id)?;
+            let permuted_id = Identifier::new(
+                &texture_id.namespace,
+                format!(
+                    "{}{}{}",
+                    texture_id.path.to_string_lossy(),
+                    separator,
+                    permutation_name
+                ),
+            );
+
+            generated.push((permuted_id, permute(&texture, &mapping)));
+        }
+    }
+
+    Ok(generated)
+}
+
+fn load_texture(
+    id: &Identifier,
+    texture_files: &HashMap<Identifier, PathBuf>,
+    generated_textures: &HashMap<Identifier, RgbaImage>,
+    vanilla: Option<&VanillaAssets>,
+) -> anyhow::Result<RgbaImage> {
+    match generated_textures.get(id) {
+        Some(texture) => Ok(texture.clone()),
+        None => vanilla::load_texture(id, texture_files, vanilla),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use image::{ImageFormat, Rgba};
+
+    use super::*;
+
+    fn image(pixels: &[[u8; 4]]) -> RgbaImage {
+        RgbaImage::from_fn(pixels.len() as u32, 1, |x, _| Rgba(pixels[x as usize]))
+    }
+
+    fn png(pixels: &[[u8; 4]]) -> Vec<u8> {
+        let mut raw = Cursor::new(Vec::new());
+        image(pixels).write_to(&mut raw, ImageFormat::Png).unwrap();
+        raw.into_inner()
+    }
+
+    #[test]
+    fn generate_permutation_names() {
+        let generated_textures = HashMap::from([
+            (
+                Identifier::minecraft("trims/color_palettes/trim_palette"),
+                image(&[[10, 10, 10, 255]]),
+            ),
+            (
+                Identifier::minecraft("trims/color_palettes/gold"),
+                image(&[[250, 200, 0, 255]]),
+            ),
+            (
+                Identifier::minecraft("trims/entity/humanoid/coast"),
+                image(&[[10, 10, 10, 255]]),
+            ),
+        ]);
+
+        let generated = generate_permutations(
+            &[Identifier::minecraft("trims/entity/humanoid/coast")],
+            &Identifier::minecraft("trims/color_palettes/trim_palette"),
+            &IndexMap::from([(
+                "gold".to_string(),
+                Identifier::minecraft("trims/color_palettes/gold"),
+            )]),
+            DEFAULT_SEPARATOR,
+            &HashMap::new(),
+            &generated_textures,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(
+            generated,
+            vec![(
+                Identifier::minecraft("trims/entity/humanoid/coast_gold"),
+                image(&[[250, 200, 0, 255]]),
+            )]
+        );
+    }
+
+    #[test]
+    fn generate_from_vanilla_textures() {
+        let vanilla = VanillaAssets {
+            textures: HashMap::from([
+                (
+                    Identifier::minecraft("trims/color_palettes/trim_palette"),
+                    png(&[[10, 10, 10, 255]]),
+                ),
+                (
+                    Identifier::minecraft("trims/color_palettes/gold"),
+                    png(&[[250, 200, 0, 255]]),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let generated_textures = HashMap::from([(
+            Identifier::new("quark", "trims/entity/humanoid/rune"),
+            image(&[[10, 10, 10, 255]]),
+        )]);
+
+        let generated = generate_permutations(
+            &[Identifier::new("quark", "trims/entity/humanoid/rune")],
+            &Identifier::minecraft("trims/color_palettes/trim_palette"),
+            &IndexMap::from([(
+                "gold".to_string(),
+                Identifier::minecraft("trims/color_palettes/gold"),
+            )]),
+            DEFAULT_SEPARATOR,
+            &HashMap::new(),
+            &generated_textures,
+            Some(&vanilla),
+        )
+        .unwrap();
+
+        assert_eq!(
+            generated,
+            vec![(
+                Identifier::new("quark", "trims/entity/humanoid/rune_gold"),
+                image(&[[250, 200, 0, 255]]),
+            )]
+        );
+    }
+}
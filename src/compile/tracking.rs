@@ -4,6 +4,7 @@ use std::{
 };
 
 use anyhow::Context;
+use indexmap::IndexMap;
 use walkdir::WalkDir;
 
 #[derive(Debug, Default)]
@@ -41,8 +42,13 @@ impl AssetTracker {
         Ok(())
     }
 
-    pub fn condence(&self, bundle_order: &Vec<PathBuf>) -> anyhow::Result<Vec<PathBuf>> {
-        let mut map = HashMap::new();
+    /// Groups every tracked copy of an asset by its path, in bundle order.
+    /// The last copy is the one that overrides the rest.
+    pub fn condence(
+        &self,
+        bundle_order: &Vec<PathBuf>,
+    ) -> anyhow::Result<IndexMap<PathBuf, Vec<PathBuf>>> {
+        let mut map = IndexMap::<PathBuf, Vec<PathBuf>>::new();
 
         for bundle in bundle_order {
             let paths = self
@@ -51,14 +57,13 @@ impl AssetTracker {
                 .with_context(|| format!("Failed to find tracked bundle: {}", bundle.display()))?;
 
             for path in paths {
-                map.insert(path, bundle.clone());
+                map.entry(path.clone())
+                    .or_default()
+                    .push(PathBuf::from(bundle).join(path));
             }
         }
 
-        Ok(map
-            .iter()
-            .map(|(path, bundle)| PathBuf::from(bundle).join(path))
-            .collect())
+        Ok(map)
     }
 }
 
@@ -91,14 +96,25 @@ mod tests {
             ]),
         };
 
+        let condenced = asset_tracker.condence(&order).unwrap();
+        let overrides = condenced
+            .values()
+            .filter_map(|copies| copies.last().cloned())
+            .collect::<HashSet<PathBuf>>();
+
         let expected = HashSet::from([
             "bundle2/minecraft/blockstates/dirt.json".into(),
             "bundle2/minecraft/models/block/dirt.json".into(),
             "bundle1/minecraft/models/block/grass_block.json".into(),
         ]);
 
-        let condenced = asset_tracker.condence(&order).unwrap();
-
-        assert_eq!(HashSet::from_iter(condenced), expected);
+        assert_eq!(overrides, expected);
+        assert_eq!(
+            condenced[&PathBuf::from("minecraft/blockstates/dirt.json")],
+            vec![
+                PathBuf::from("bundle1/minecraft/blockstates/dirt.json"),
+                PathBuf::from("bundle2/minecraft/blockstates/dirt.json"),
+            ]
+        );
     }
 }
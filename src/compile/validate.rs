@@ -0,0 +1,55 @@
+use std::fmt::Display;
+
+use anyhow::bail;
+
+use super::{library::CompiledAssetLibrary, PackCompiler};
+
+pub mod blockstate;
+pub mod model;
+pub mod texture;
+
+/// Problems found while validating; errors fail the build once every check has run.
+#[derive(Debug, Default)]
+pub struct ValidationReport {
+    warnings: Vec<String>,
+    errors: Vec<String>,
+}
+
+impl ValidationReport {
+    pub fn warn(&mut self, subject: impl Display, message: impl Display) {
+        self.warnings.push(format!("\"{}\": {}", subject, message));
+    }
+
+    pub fn error(&mut self, subject: impl Display, message: impl Display) {
+        self.errors.push(format!("\"{}\": {}", subject, message));
+    }
+
+    pub fn finish(self) -> anyhow::Result<()> {
+        for warning in &self.warnings {
+            println!("[WARNING] {}", warning);
+        }
+
+        if !self.errors.is_empty() {
+            bail!("Validation failed:\n{}", self.errors.join("\n"));
+        }
+
+        Ok(())
+    }
+}
+
+impl PackCompiler<'_> {
+    pub fn validate(&self, library: &CompiledAssetLibrary) -> anyhow::Result<()> {
+        let mut report = ValidationReport::default();
+
+        library.validate_textures(&mut report);
+        library.validate_models(self.vanilla.as_deref(), &mut report);
+        library.validate_blockstates(self.vanilla.as_deref(), &mut report);
+        library.validate_element_rotations(
+            self.pack.format.clone().unwrap_or_default().min(),
+            &mut report,
+        );
+        self.validate_icon(&mut report)?;
+
+        report.finish()
+    }
+}
@@ -0,0 +1,99 @@
+use crate::compile::{library::CompiledAssetLibrary, vanilla::VanillaAssets};
+
+use super::ValidationReport;
+
+impl CompiledAssetLibrary {
+    /// Checks that every blockstate references models that exist.
+    ///
+    /// Without vanilla assets, models in the minecraft namespace are assumed to exist.
+    pub fn validate_blockstates(
+        &self,
+        vanilla: Option<&VanillaAssets>,
+        report: &mut ValidationReport,
+    ) {
+        for (id, blockstate) in &self.blockstates {
+            if let Some(vanilla) = vanilla {
+                if id.is_minecraft() && !vanilla.blockstates.contains_key(id) {
+                    report.warn(id, "Block doesn't exist in vanilla");
+                }
+            }
+
+            for model_id in blockstate.models() {
+                let in_vanilla = match vanilla {
+                    Some(vanilla) => vanilla.has_model(model_id),
+                    None => model_id.is_minecraft(),
+                };
+
+                if !in_vanilla && !self.models.contains_key(model_id) {
+                    report.warn(id, format!("Model doesn't exist: {}", model_id));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use serde_json::json;
+
+    use crate::minecraft::asset::{
+        blockstate::Blockstate, model::Model, types::identifier::Identifier,
+    };
+
+    use super::*;
+
+    fn validate(
+        blockstate: serde_json::Value,
+        vanilla: Option<&VanillaAssets>,
+    ) -> ValidationReport {
+        let library = CompiledAssetLibrary {
+            blockstates: HashMap::from([(
+                Identifier::minecraft("glass"),
+                serde_json::from_value::<Blockstate>(blockstate).unwrap(),
+            )]),
+            models: HashMap::from([(Identifier::new("quark", "block/glass"), Model::default())]),
+            ..Default::default()
+        };
+
+        let mut report = ValidationReport::default();
+        library.validate_blockstates(vanilla, &mut report);
+        report
+    }
+
+    #[test]
+    fn unknown_model() {
+        let report = validate(
+            json!({
+                "variants": {
+                    "": [{ "model": "quark:block/glass" }, { "model": "quark:block/glas" }]
+                }
+            }),
+            None,
+        );
+
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn vanilla_models() {
+        let vanilla = VanillaAssets {
+            models: HashMap::from([(Identifier::minecraft("block/glass"), Model::default())]),
+            ..Default::default()
+        };
+
+        let report = validate(
+            json!({
+                "multipart": [
+                    { "apply": { "model": "block/glass" } },
+                    { "apply": { "model": "block/glas" } }
+                ]
+            }),
+            Some(&vanilla),
+        );
+
+        // The typo and the block missing from vanilla.
+        assert_eq!(report.warnings.len(), 2);
+    }
+}
@@ -0,0 +1,441 @@
+use std::collections::HashSet;
+
+use indexmap::IndexMap;
+
+use crate::{
+    compile::{library::CompiledAssetLibrary, vanilla::VanillaAssets},
+    minecraft::asset::{
+        model::{ElementRotation, IdentifierOrVariable, Model},
+        types::{identifier::Identifier, variable::VariableIdentifier},
+        Asset,
+    },
+};
+
+use super::ValidationReport;
+
+const PARTICLE_VARIABLE: &str = "particle";
+/// Parents such as `builtin/generated` are built into the game instead of the jar.
+const BUILTIN_FOLDER: &str = "builtin";
+
+impl CompiledAssetLibrary {
+    /// Checks the textures of every model that isn't a parent of another.
+    ///
+    /// Parents and textures may come from the vanilla assets. Models whose parent chain
+    /// can't be followed only have their own textures checked; the missing parents may define
+    /// the variables. Without vanilla assets, textures in the minecraft namespace are assumed
+    /// to exist. Unresolved variables are warnings, as raw models may only be imported as parts.
+    pub fn validate_models(&self, vanilla: Option<&VanillaAssets>, report: &mut ValidationReport) {
+        let parents = self
+            .models
+            .values()
+            .filter_map(|model| model.parent.as_ref())
+            .collect::<HashSet<_>>();
+
+        for (id, model) in &self.models {
+            if model.is_virtual() || parents.contains(id) {
+                continue;
+            }
+
+            match self.parent_chain(id, model, vanilla, report) {
+                Some(chain) => self.validate_model_textures(id, &chain, vanilla, report),
+                None => {
+                    let textures = model
+                        .textures
+                        .iter()
+                        .map(|(name, texture)| (name.as_str(), texture));
+                    self.validate_texture_ids(id, textures, vanilla, report);
+                }
+            }
+        }
+    }
+
+    /// Checks that the game accepts the rotation angle of every element in the pack format.
+    pub fn validate_element_rotations(&self, pack_format: u8, report: &mut ValidationReport) {
+        for (id, model) in &self.models {
+            let angles = model
+                .elements
+                .iter()
+                .filter_map(|element| element.rotation.as_ref())
+                .map(|rotation| rotation.angle);
+
+            for angle in angles {
+                if !ElementRotation::is_angle_allowed(angle, pack_format) {
+                    report.error(
+                        id,
+                        format!(
+                            "Element rotation angle {} isn't allowed in pack format {}",
+                            angle, pack_format
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    /// The model followed by each of its parents; `None` if any can't be found.
+    fn parent_chain<'a>(
+        &'a self,
+        id: &Identifier,
+        model: &'a Model,
+        vanilla: Option<&'a VanillaAssets>,
+        report: &mut ValidationReport,
+    ) -> Option<Vec<&'a Model>> {
+        let mut chain = vec![model];
+        let mut visited = HashSet::from([id]);
+        let mut current = model;
+
+        while let Some(parent_id) = &current.parent {
+            if !visited.insert(parent_id) {
+                report.error(id, format!("Parent models form a cycle at: {}", parent_id));
+                return None;
+            }
+
+            current = match self.lookup_model(parent_id, vanilla) {
+                Some(parent) => parent,
+                None => {
+                    let builtin = parent_id.starts_with(&Identifier::minecraft(BUILTIN_FOLDER));
+
+                    if vanilla.is_some() && parent_id.is_minecraft() && !builtin {
+                        report.warn(id, format!("Parent model doesn't exist: {}", parent_id));
+                    }
+
+                    return None;
+                }
+            };
+            chain.push(current);
+        }
+
+        Some(chain)
+    }
+
+    fn validate_model_textures(
+        &self,
+        id: &Identifier,
+        chain: &[&Model],
+        vanilla: Option<&VanillaAssets>,
+        report: &mut ValidationReport,
+    ) {
+        // Children override the textures of their parents.
+        let mut textures = IndexMap::new();
+
+        for model in chain.iter().rev() {
+            textures.extend(
+                model
+                    .textures
+                    .iter()
+                    .map(|(name, texture)| (name.as_str(), texture)),
+            );
+        }
+
+        self.validate_texture_ids(
+            id,
+            textures.iter().map(|(name, texture)| (*name, *texture)),
+            vanilla,
+            report,
+        );
+
+        let elements = chain
+            .iter()
+            .map(|model| &model.elements)
+            .find(|elements| !elements.is_empty());
+
+        let face_variables = elements
+            .into_iter()
+            .flatten()
+            .flat_map(|element| (&element.faces).into_iter().flatten())
+            .map(|face| face.texture.get_name());
+        let particle = textures
+            .contains_key(PARTICLE_VARIABLE)
+            .then_some(PARTICLE_VARIABLE);
+
+        let mut checked = HashSet::new();
+
+        for variable in face_variables.chain(particle) {
+            if !checked.insert(variable) {
+                continue;
+            }
+
+            if let Err(message) = resolve_variable(variable, &textures) {
+                report.warn(id, message);
+            }
+        }
+    }
+
+    /// Warns about every texture that doesn't exist, whether an element uses it or not.
+    fn validate_texture_ids<'a>(
+        &self,
+        id: &Identifier,
+        textures: impl IntoIterator<Item = (&'a str, &'a IdentifierOrVariable)>,
+        vanilla: Option<&VanillaAssets>,
+        report: &mut ValidationReport,
+    ) {
+        let mut checked = HashSet::new();
+
+        for (name, texture) in textures {
+            let IdentifierOrVariable::Identifier(texture_id) = texture else {
+                continue;
+            };
+
+            if checked.insert(texture_id) && !self.has_texture(texture_id, vanilla) {
+                report.warn(
+                    id,
+                    format!("Texture \"{}\" doesn't exist: #{}", texture_id, name),
+                );
+            }
+        }
+    }
+
+    fn has_texture(&self, id: &Identifier, vanilla: Option<&VanillaAssets>) -> bool {
+        let in_vanilla = match vanilla {
+            Some(vanilla) => vanilla.has_texture(id),
+            None => id.is_minecraft(),
+        };
+
+        in_vanilla || self.textures.contains_key(id) || self.generated_textures.contains_key(id)
+    }
+}
+
+fn resolve_variable<'a>(
+    variable: &str,
+    textures: &IndexMap<&str, &'a IdentifierOrVariable>,
+) -> Result<&'a Identifier, String> {
+    let mut visited = vec![variable];
+    let mut current = variable;
+
+    loop {
+        match textures.get(current) {
+            Some(IdentifierOrVariable::Identifier(id)) => return Ok(id),
+            Some(IdentifierOrVariable::Variable(next)) => {
+                let next = next.get_name();
+
+                if visited.contains(&next) {
+                    visited.push(next);
+                    let cycle = visited
+                        .iter()
+                        .map(|name| VariableIdentifier::new(name.to_string()).to_string())
+                        .collect::<Vec<_>>();
+                    return Err(format!(
+                        "Texture variables form a cycle: {}",
+                        cycle.join(" -> ")
+                    ));
+                }
+
+                visited.push(next);
+                current = next;
+            }
+            None => return Err(format!("Texture variable is never defined: #{}", current)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, path::PathBuf};
+
+    use serde_json::json;
+
+    use super::*;
+
+    fn validate(models: serde_json::Value) -> ValidationReport {
+        validate_with(models, None)
+    }
+
+    fn validate_with(
+        models: serde_json::Value,
+        vanilla: Option<&VanillaAssets>,
+    ) -> ValidationReport {
+        let models = serde_json::from_value::<HashMap<String, Model>>(models)
+            .unwrap()
+            .into_iter()
+            .map(|(id, model)| (id.parse().unwrap(), model))
+            .collect();
+
+        let library = CompiledAssetLibrary {
+            models,
+            textures: HashMap::from([(
+                Identifier::new("quark", "block/glass"),
+                PathBuf::from("glass.png"),
+            )]),
+            ..Default::default()
+        };
+
+        let mut report = ValidationReport::default();
+        library.validate_models(vanilla, &mut report);
+        report
+    }
+
+    #[test]
+    fn element_rotation_angles() {
+        let models = serde_json::from_value::<HashMap<String, Model>>(json!({
+            "quark:block/chair": {
+                "elements": [{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "rotation": { "origin": [8, 8, 8], "axis": "z", "angle": 30 }
+                }]
+            }
+        }))
+        .unwrap()
+        .into_iter()
+        .map(|(id, model)| (id.parse().unwrap(), model))
+        .collect();
+
+        let library = CompiledAssetLibrary {
+            models,
+            ..Default::default()
+        };
+
+        let mut report = ValidationReport::default();
+        library.validate_element_rotations(ElementRotation::FREE_ANGLE_FORMAT - 1, &mut report);
+        assert_eq!(report.errors.len(), 1);
+
+        let mut report = ValidationReport::default();
+        library.validate_element_rotations(ElementRotation::FREE_ANGLE_FORMAT, &mut report);
+        assert!(report.errors.is_empty());
+    }
+
+    #[test]
+    fn resolved_through_parent() {
+        let report = validate(json!({
+            "quark:block/template": {
+                "textures": { "particle": "#all" },
+                "elements": [{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "faces": { "up": { "texture": "#all" } }
+                }]
+            },
+            "quark:block/glass": {
+                "parent": "quark:block/template",
+                "textures": { "all": "quark:block/glass" }
+            }
+        }));
+
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn unresolved_variable() {
+        let report = validate(json!({
+            "quark:block/glass": {
+                "textures": { "all": "quark:block/glass" },
+                "elements": [{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "faces": { "up": { "texture": "#top" } }
+                }]
+            }
+        }));
+
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn variable_cycle() {
+        let report = validate(json!({
+            "quark:block/glass": {
+                "textures": { "all": "#side", "side": "#all" },
+                "elements": [{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "faces": { "up": { "texture": "#all" }, "down": { "texture": "#side" } }
+                }]
+            }
+        }));
+
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 2);
+    }
+
+    #[test]
+    fn unknown_texture() {
+        let report = validate(json!({
+            "quark:block/glass": {
+                "textures": { "all": "quark:block/glas" },
+                "elements": [{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "faces": { "up": { "texture": "#all" } }
+                }]
+            }
+        }));
+
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn unreferenced_texture() {
+        let report = validate(json!({
+            "quark:block/glass": {
+                "textures": { "all": "quark:block/glass", "side": "quark:block/glas" }
+            },
+            "quark:item/glass": {
+                "parent": "item/generated",
+                "textures": { "layer0": "quark:block/glass", "layer1": "quark:item/glas" }
+            }
+        }));
+
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 2);
+    }
+
+    #[test]
+    fn parent_outside_pack() {
+        let report = validate(json!({
+            "quark:block/glass": {
+                "parent": "block/cube_all",
+                "textures": { "top": "quark:block/glass" }
+            }
+        }));
+
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn vanilla_parent() {
+        let vanilla = VanillaAssets {
+            models: serde_json::from_value::<HashMap<String, Model>>(json!({
+                "block/cube_all": {
+                    "elements": [{
+                        "from": [0, 0, 0],
+                        "to": [16, 16, 16],
+                        "faces": { "up": { "texture": "#all" } }
+                    }]
+                }
+            }))
+            .unwrap()
+            .into_iter()
+            .map(|(id, model)| (id.parse().unwrap(), model))
+            .collect(),
+            textures: HashMap::from([(Identifier::minecraft("block/stone"), Vec::new())]),
+            ..Default::default()
+        };
+
+        let report = validate_with(
+            json!({
+                "quark:block/stone": {
+                    "parent": "block/cube_all",
+                    "textures": { "all": "block/stone" }
+                },
+                "quark:block/stne": {
+                    "parent": "block/cube_all",
+                    "textures": { "all": "block/stne" }
+                },
+                "quark:block/glass": {
+                    "parent": "block/cube_al"
+                },
+                "quark:item/glass": {
+                    "parent": "builtin/generated"
+                }
+            }),
+            Some(&vanilla),
+        );
+
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 2);
+    }
+}
@@ -0,0 +1,178 @@
+use image::GenericImageView;
+
+use crate::{
+    compile::{library::CompiledAssetLibrary, PackCompiler},
+    minecraft::asset::{texture::TextureMeta, types::identifier::Identifier},
+};
+
+use super::ValidationReport;
+
+/// Larger textures exceed what most GPUs can hold in a single texture.
+const MAX_DIMENSION: u32 = 16384;
+
+impl CompiledAssetLibrary {
+    pub fn validate_textures(&self, report: &mut ValidationReport) {
+        for (id, path) in &self.textures {
+            match image::open(path) {
+                Ok(texture) => {
+                    validate_texture(id, texture.dimensions(), self.textures_meta.get(id), report)
+                }
+                Err(e) => report.error(id, format!("Failed to decode texture: {}", e)),
+            }
+        }
+
+        for (id, texture) in &self.generated_textures {
+            validate_texture(id, texture.dimensions(), self.textures_meta.get(id), report);
+        }
+    }
+}
+
+impl PackCompiler<'_> {
+    pub fn validate_icon(&self, report: &mut ValidationReport) -> anyhow::Result<()> {
+        let Some(icon) = &self.pack.icon else {
+            return Ok(());
+        };
+
+        let icon_path = self.project_sanitizer.join(icon)?;
+
+        match image::open(&icon_path) {
+            Ok(texture) if texture.width() != texture.height() => report.warn(
+                icon.display(),
+                format!(
+                    "Pack icon is {}x{}; it should be square",
+                    texture.width(),
+                    texture.height()
+                ),
+            ),
+            Ok(_) => (),
+            Err(e) => report.error(icon.display(), format!("Failed to decode pack icon: {}", e)),
+        }
+
+        Ok(())
+    }
+}
+
+fn validate_texture(
+    id: &Identifier,
+    (width, height): (u32, u32),
+    meta: Option<&TextureMeta>,
+    report: &mut ValidationReport,
+) {
+    if width == 0 || height == 0 {
+        report.error(id, "Texture has no pixels");
+        return;
+    }
+
+    if width > MAX_DIMENSION || height > MAX_DIMENSION {
+        report.warn(
+            id,
+            format!(
+                "Texture is {}x{}; larger than {} pixels may fail to load",
+                width, height, MAX_DIMENSION
+            ),
+        );
+    }
+
+    let Some(animation) = meta.and_then(|meta| meta.animation.as_ref()) else {
+        return;
+    };
+
+    let (frame_width, frame_height) = animation.frame_size(width, height);
+
+    if frame_width == 0 || frame_height == 0 {
+        report.error(id, "Animation frames have no pixels");
+        return;
+    }
+
+    if width % frame_width != 0 || height % frame_height != 0 {
+        report.error(
+            id,
+            format!(
+                "Texture is {}x{}; not a whole multiple of its {}x{} frames",
+                width, height, frame_width, frame_height
+            ),
+        );
+        return;
+    }
+
+    let frame_count = (width / frame_width) * (height / frame_height);
+
+    for frame in animation.frames.iter().flatten() {
+        if frame.index() >= frame_count {
+            report.error(
+                id,
+                format!(
+                    "Animation frame {} is out of range; the texture has {} frames",
+                    frame.index(),
+                    frame_count
+                ),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::minecraft::asset::texture::{AnimationFrame, TextureAnimation};
+
+    use super::*;
+
+    fn validate(dimensions: (u32, u32), animation: Option<TextureAnimation>) -> ValidationReport {
+        let mut report = ValidationReport::default();
+        let meta = TextureMeta {
+            animation,
+            ..Default::default()
+        };
+        validate_texture(
+            &Identifier::minecraft("block/magma"),
+            dimensions,
+            Some(&meta),
+            &mut report,
+        );
+        report
+    }
+
+    #[test]
+    fn valid_animation() {
+        let report = validate(
+            (16, 48),
+            Some(TextureAnimation {
+                frames: Some(vec![
+                    AnimationFrame::Index(2),
+                    AnimationFrame::IndexTime { index: 0, time: 4 },
+                ]),
+                ..Default::default()
+            }),
+        );
+
+        assert!(report.errors.is_empty());
+        assert!(report.warnings.is_empty());
+    }
+
+    #[test]
+    fn animation_height_not_multiple() {
+        let report = validate((16, 40), Some(TextureAnimation::default()));
+        assert_eq!(report.errors.len(), 1);
+    }
+
+    #[test]
+    fn animation_frame_out_of_range() {
+        let report = validate(
+            (16, 32),
+            Some(TextureAnimation {
+                frames: Some(vec![AnimationFrame::Index(1), AnimationFrame::Index(2)]),
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(report.errors.len(), 1);
+    }
+
+    #[test]
+    fn oversized_texture() {
+        let report = validate((32768, 16), None);
+
+        assert!(report.errors.is_empty());
+        assert_eq!(report.warnings.len(), 1);
+    }
+}
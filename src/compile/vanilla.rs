@@ -0,0 +1,194 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Context;
+use async_zip::base::read::mem::ZipFileReader;
+use image::RgbaImage;
+
+use crate::minecraft::asset::{
+    blockstate::Blockstate,
+    model::Model,
+    types::identifier::{AssetType, Identifier},
+};
+
+const JAR_ASSETS_FOLDER: &str = "assets";
+
+/// Assets of the vanilla client jar.
+///
+/// They can be referenced and imported by the pack, but are never written to the output.
+#[derive(Debug, Default)]
+pub struct VanillaAssets {
+    pub models: HashMap<Identifier, Model>,
+    pub blockstates: HashMap<Identifier, Blockstate>,
+    pub textures: HashMap<Identifier, Vec<u8>>,
+}
+
+impl VanillaAssets {
+    pub async fn load(minecraft_path: &Path, version: &str) -> anyhow::Result<Self> {
+        let jar_path = Self::jar_path(minecraft_path, version);
+
+        let raw = async_fs::read(&jar_path).await.with_context(|| {
+            format!("Failed to read vanilla client jar: {}", jar_path.display())
+        })?;
+
+        Self::from_jar(raw)
+            .await
+            .with_context(|| format!("Failed to load vanilla assets: {}", jar_path.display()))
+    }
+
+    fn jar_path(minecraft_path: &Path, version: &str) -> PathBuf {
+        minecraft_path
+            .join("versions")
+            .join(version)
+            .join(format!("{}.jar", version))
+    }
+
+    async fn from_jar(raw: Vec<u8>) -> anyhow::Result<Self> {
+        let jar = ZipFileReader::new(raw).await?;
+        let mut assets = Self::default();
+        let mut skipped = 0;
+
+        for (index, entry) in jar.file().entries().iter().enumerate() {
+            let Some(asset_path) = entry
+                .filename()
+                .as_str()
+                .ok()
+                .and_then(|name| Path::new(name).strip_prefix(JAR_ASSETS_FOLDER).ok())
+            else {
+                continue;
+            };
+
+            let Ok((asset_type, id)) = Identifier::from_path(asset_path) else {
+                continue;
+            };
+
+            if !matches!(
+                asset_type,
+                AssetType::Model | AssetType::Blockstate | AssetType::Texture
+            ) {
+                continue;
+            }
+
+            let mut raw = Vec::new();
+            jar.reader_with_entry(index)
+                .await?
+                .read_to_end_checked(&mut raw)
+                .await?;
+
+            let parsed = match asset_type {
+                AssetType::Model => serde_json::from_slice(&raw)
+                    .map(|model| assets.models.insert(id, model))
+                    .is_ok(),
+                AssetType::Blockstate => serde_json::from_slice(&raw)
+                    .map(|blockstate| assets.blockstates.insert(id, blockstate))
+                    .is_ok(),
+                _ => {
+                    assets.textures.insert(id, raw);
+                    true
+                }
+            };
+
+            if !parsed {
+                skipped += 1;
+            }
+        }
+
+        if skipped > 0 {
+            println!(
+                "[WARNING] Skipped {} vanilla assets that failed to parse.",
+                skipped
+            );
+        }
+
+        Ok(assets)
+    }
+
+    pub fn has_model(&self, id: &Identifier) -> bool {
+        self.models.contains_key(id)
+    }
+
+    pub fn has_texture(&self, id: &Identifier) -> bool {
+        self.textures.contains_key(id)
+    }
+}
+
+/// Decodes a texture; pack textures take priority over the vanilla ones they replace.
+pub fn load_texture(
+    id: &Identifier,
+    textures: &HashMap<Identifier, PathBuf>,
+    vanilla: Option<&VanillaAssets>,
+) -> anyhow::Result<RgbaImage> {
+    let texture = if let Some(path) = textures.get(id) {
+        image::open(path)
+    } else {
+        let raw = vanilla
+            .and_then(|vanilla| vanilla.textures.get(id))
+            .with_context(|| format!("Failed to lookup texture: {}", id))?;
+        image::load_from_memory(raw)
+    };
+
+    Ok(texture
+        .with_context(|| format!("Failed to decode texture: {}", id))?
+        .into_rgba8())
+}
+
+#[cfg(test)]
+mod tests {
+    use async_zip::{base::write::ZipFileWriter, Compression, ZipEntryBuilder};
+
+    use super::*;
+
+    async fn jar(entries: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = ZipFileWriter::new(Vec::new());
+
+        for (name, contents) in entries {
+            let entry = ZipEntryBuilder::new(name.to_string().into(), Compression::Deflate);
+            writer
+                .write_entry_whole(entry, contents.as_bytes())
+                .await
+                .unwrap();
+        }
+
+        writer.close().await.unwrap()
+    }
+
+    #[test]
+    fn vanilla_jar_path() {
+        assert_eq!(
+            VanillaAssets::jar_path(Path::new(".minecraft"), "1.21.1"),
+            PathBuf::from(".minecraft/versions/1.21.1/1.21.1.jar")
+        );
+    }
+
+    #[tokio::test]
+    async fn load_vanilla_assets() {
+        let raw = jar(&[
+            ("net/minecraft/client/main/Main.class", ""),
+            (
+                "assets/minecraft/models/block/cube_all.json",
+                r#"{ "parent": "block/cube" }"#,
+            ),
+            (
+                "assets/minecraft/blockstates/stone.json",
+                r#"{ "variants": { "": { "model": "block/stone" } } }"#,
+            ),
+            ("assets/minecraft/textures/block/stone.png", "png"),
+            ("assets/minecraft/lang/en_us.json", "{}"),
+        ])
+        .await;
+
+        let assets = VanillaAssets::from_jar(raw).await.unwrap();
+
+        assert!(assets.has_model(&Identifier::minecraft("block/cube_all")));
+        assert!(assets
+            .blockstates
+            .contains_key(&Identifier::minecraft("stone")));
+        assert!(assets.has_texture(&Identifier::minecraft("block/stone")));
+        assert_eq!(
+            assets.models.len() + assets.blockstates.len() + assets.textures.len(),
+            3
+        );
+    }
+}
@@ -2,17 +2,21 @@ pub mod export;
 
 use std::{
     collections::{BTreeSet, HashMap},
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::Arc,
 };
 
 use anyhow::Context;
 use ferinth::structures::version::Dependency;
+use glob::{MatchOptions, Pattern};
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
-use serde_with::{serde_as, OneOrMany};
+use serde_with::{serde_as, DisplayFromStr, OneOrMany};
 
-use crate::minecraft::asset::types::text::RawText;
+use crate::{
+    asset::{merge::MergeStrategy, selector::AssetSelector},
+    minecraft::asset::types::{identifier::AssetType, text::RawText},
+};
 
 use self::export::{ExportOutputType, ExportRelocation, JsonExportType};
 
@@ -142,9 +146,25 @@ pub struct ProfileConfig {
     pub output_type: ExportOutputType,
     pub relocation: ExportRelocation,
     pub json_type: JsonExportType,
+    /// Re-encodes every PNG losslessly to reduce the pack's size.
+    pub optimize_png: bool,
+    pub minify: Option<MinifyConfig>,
     pub pack: PackMetaConfig,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct MinifyConfig {
+    /// Decimal places kept for coordinates, UVs and rotation angles.
+    pub precision: u8,
+}
+
+impl Default for MinifyConfig {
+    fn default() -> Self {
+        Self { precision: 4 }
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Deserialize, Default, Clone)]
 #[serde(default)]
@@ -156,6 +176,39 @@ pub struct CollectionConfig {
     #[serde_as(as = "OneOrMany<_>")]
     #[serde(default)]
     pub minecraft_versions: Vec<String>,
+    pub passthrough: PassthroughConfig,
+    /// The merge strategy used for each asset type when a file doesn't specify one.
+    pub merge: HashMap<AssetType, MergeStrategy>,
+    /// Generates `paletted_permutations` textures at compile time for older formats.
+    pub emulate_paletted_permutations: bool,
+    /// Models that have their parents inlined before modifiers run.
+    pub flatten: Option<AssetSelector>,
+}
+
+/// Controls which unrecognized files are copied verbatim into the pack.
+#[serde_as]
+#[derive(Debug, Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct PassthroughConfig {
+    /// Glob patterns relative to the bundle; when empty, every file is allowed.
+    #[serde_as(as = "OneOrMany<DisplayFromStr>")]
+    pub allow: Vec<Pattern>,
+    #[serde_as(as = "OneOrMany<DisplayFromStr>")]
+    pub deny: Vec<Pattern>,
+}
+
+impl PassthroughConfig {
+    const MATCH_OPTIONS: MatchOptions = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    pub fn allows(&self, path: &Path) -> bool {
+        let matches = |pattern: &Pattern| pattern.matches_path_with(path, Self::MATCH_OPTIONS);
+
+        !self.deny.iter().any(matches) && (self.allow.is_empty() || self.allow.iter().any(matches))
+    }
 }
 
 #[derive(Debug, Deserialize, Default, Clone)]
@@ -164,6 +217,8 @@ pub struct PackConfig {
     pub pack: PackMetaConfig,
     pub profile: HashMap<String, ProfileConfig>,
     pub build: IndexMap<String, CollectionConfig>,
+    /// Version of the client jar under `<minecraft>/versions` used to resolve vanilla assets.
+    pub vanilla: Option<String>,
 }
 
 impl PackConfig {
@@ -188,3 +243,30 @@ impl PackConfig {
         PackMetaConfig::condence(self.pack.clone(), build.clone(), profile.clone())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn passthrough_allow_all() {
+        let config = PassthroughConfig::default();
+        assert!(config.allows(Path::new("minecraft/shaders/core/rendertype_solid.json")));
+    }
+
+    #[test]
+    fn passthrough_allow_deny() {
+        let config: PassthroughConfig = toml::from_str(
+            r#"
+            allow = ["*/shaders/**", "*/optifine/**"]
+            deny = "*/optifine/cit/**"
+            "#,
+        )
+        .unwrap();
+
+        assert!(config.allows(Path::new("minecraft/shaders/core/rendertype_solid.json")));
+        assert!(config.allows(Path::new("minecraft/optifine/ctm/glass/1.png")));
+        assert!(!config.allows(Path::new("minecraft/optifine/cit/sword.properties")));
+        assert!(!config.allows(Path::new("minecraft/texts/splashes.txt")));
+    }
+}
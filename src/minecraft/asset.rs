@@ -2,7 +2,10 @@ use self::types::identifier::AssetType;
 
 pub mod atlas;
 pub mod blockstate;
+pub mod font;
+pub mod language;
 pub mod model;
+pub mod sound;
 pub mod texture;
 pub mod types;
 
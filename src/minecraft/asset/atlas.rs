@@ -1,17 +1,18 @@
-use std::path::PathBuf;
+use std::{fmt, path::PathBuf, str::FromStr};
 
 use indexmap::IndexMap;
+use regex::Regex;
 use serde::{Deserialize, Serialize};
-use serde_with::skip_serializing_none;
+use serde_with::{serde_as, skip_serializing_none, DisplayFromStr};
 
 use super::{
     types::identifier::{AssetType, Identifier},
     Asset,
 };
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Default, PartialEq, Deserialize, Serialize)]
 pub struct Atlas {
-    sources: Vec<AtlasSource>,
+    pub sources: Vec<AtlasSource>,
 }
 
 impl Asset for Atlas {
@@ -21,30 +22,286 @@ impl Asset for Atlas {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AtlasSource {
-    Directory {
-        source: PathBuf,
-        prefix: String,
-    },
+    #[serde(alias = "minecraft:directory")]
+    Directory { source: PathBuf, prefix: String },
+    #[serde(alias = "minecraft:single")]
     Single {
-        resource: PathBuf,
-        sprite: Option<String>,
-    },
-    Filter {
-        namespace: Option<String>,
-        path: Option<PathBuf>,
+        resource: Identifier,
+        sprite: Option<Identifier>,
     },
-    Unstich {
-        resource: PathBuf,
+    #[serde(alias = "minecraft:filter")]
+    Filter { pattern: AtlasFilterPattern },
+    #[serde(alias = "minecraft:unstitch", alias = "unstich")]
+    Unstitch {
+        resource: Identifier,
+        #[serde(default = "AtlasSource::default_divisor")]
         divisor_x: f64,
+        #[serde(default = "AtlasSource::default_divisor")]
         divisor_y: f64,
-        regions: Vec<()>,
+        regions: Vec<UnstitchRegion>,
     },
+    #[serde(alias = "minecraft:paletted_permutations")]
     PalettedPermutations {
         textures: Vec<Identifier>,
-        palette_key: Vec<Identifier>,
+        palette_key: Identifier,
         permutations: IndexMap<String, Identifier>,
+        /// Joins a texture's path and a permutation's name; defaults to `_` in game.
+        separator: Option<String>,
     },
 }
+
+impl AtlasSource {
+    fn default_divisor() -> f64 {
+        1.0
+    }
+}
+
+/// Removes every sprite matching both patterns; a missing pattern matches anything.
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct AtlasFilterPattern {
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub namespace: Option<WholeMatchRegex>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    #[serde(default)]
+    pub path: Option<WholeMatchRegex>,
+}
+
+impl AtlasFilterPattern {
+    pub fn matches(&self, id: &Identifier) -> bool {
+        let namespace_matches = self
+            .namespace
+            .as_ref()
+            .is_none_or(|pattern| pattern.is_match(&id.namespace));
+        let path_matches = self
+            .path
+            .as_ref()
+            .is_none_or(|pattern| pattern.is_match(&id.path.to_string_lossy()));
+
+        namespace_matches && path_matches
+    }
+}
+
+/// A pattern that has to match the whole text, as the game matches filter patterns.
+#[derive(Debug, Clone)]
+pub struct WholeMatchRegex {
+    source: String,
+    regex: Regex,
+}
+
+impl WholeMatchRegex {
+    pub fn is_match(&self, text: &str) -> bool {
+        self.regex.is_match(text)
+    }
+}
+
+impl FromStr for WholeMatchRegex {
+    type Err = regex::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            source: s.to_string(),
+            regex: Regex::new(&format!("^(?:{})$", s))?,
+        })
+    }
+}
+
+impl fmt::Display for WholeMatchRegex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl PartialEq for WholeMatchRegex {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+/// A sprite cut out of the source texture; measured in divisor units.
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct UnstitchRegion {
+    pub sprite: Identifier,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(raw: &str) -> Atlas {
+        let parsed: Atlas = serde_json::from_str(raw).unwrap();
+        let serialized = serde_json::to_string(&parsed).unwrap();
+        let reparsed: Atlas = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(parsed, reparsed);
+        parsed
+    }
+
+    #[test]
+    fn deserialize_vanilla_blocks() {
+        let atlas = round_trip(
+            r#"{
+                "sources": [
+                    { "type": "minecraft:directory", "prefix": "block/", "source": "block" },
+                    { "type": "minecraft:directory", "prefix": "item/", "source": "item" },
+                    { "type": "minecraft:single", "resource": "minecraft:entity/bell/bell_body" },
+                    { "type": "single", "resource": "entity/conduit/base", "sprite": "conduit" }
+                ]
+            }"#,
+        );
+
+        assert_eq!(
+            atlas.sources[0],
+            AtlasSource::Directory {
+                source: PathBuf::from("block"),
+                prefix: "block/".to_string(),
+            }
+        );
+        assert_eq!(
+            atlas.sources[3],
+            AtlasSource::Single {
+                resource: Identifier::minecraft("entity/conduit/base"),
+                sprite: Some(Identifier::minecraft("conduit")),
+            }
+        );
+    }
+
+    #[test]
+    fn deserialize_vanilla_armor_trims() {
+        let atlas = round_trip(
+            r#"{
+                "sources": [
+                    {
+                        "type": "minecraft:paletted_permutations",
+                        "textures": ["trims/entity/humanoid/coast", "trims/entity/humanoid/dune"],
+                        "palette_key": "trims/color_palettes/trim_palette",
+                        "permutations": {
+                            "quartz": "trims/color_palettes/quartz",
+                            "iron": "trims/color_palettes/iron"
+                        }
+                    }
+                ]
+            }"#,
+        );
+
+        assert_eq!(
+            atlas.sources[0],
+            AtlasSource::PalettedPermutations {
+                textures: vec![
+                    Identifier::minecraft("trims/entity/humanoid/coast"),
+                    Identifier::minecraft("trims/entity/humanoid/dune"),
+                ],
+                palette_key: Identifier::minecraft("trims/color_palettes/trim_palette"),
+                permutations: IndexMap::from([
+                    (
+                        "quartz".to_string(),
+                        Identifier::minecraft("trims/color_palettes/quartz")
+                    ),
+                    (
+                        "iron".to_string(),
+                        Identifier::minecraft("trims/color_palettes/iron")
+                    ),
+                ]),
+                separator: None,
+            }
+        );
+    }
+
+    #[test]
+    fn unstitch_round_trip() {
+        let atlas = round_trip(
+            r#"{
+                "sources": [
+                    {
+                        "type": "unstich",
+                        "resource": "minecraft:gui/icons",
+                        "divisor_x": 256,
+                        "divisor_y": 256,
+                        "regions": [
+                            { "sprite": "hud/heart/full", "x": 52, "y": 0, "width": 9, "height": 9 }
+                        ]
+                    }
+                ]
+            }"#,
+        );
+
+        assert_eq!(
+            atlas.sources[0],
+            AtlasSource::Unstitch {
+                resource: Identifier::minecraft("gui/icons"),
+                divisor_x: 256.0,
+                divisor_y: 256.0,
+                regions: vec![UnstitchRegion {
+                    sprite: Identifier::minecraft("hud/heart/full"),
+                    x: 52.0,
+                    y: 0.0,
+                    width: 9.0,
+                    height: 9.0,
+                }],
+            }
+        );
+
+        let serialized = serde_json::to_value(&atlas).unwrap();
+        assert_eq!(serialized["sources"][0]["type"], "unstitch");
+    }
+
+    #[test]
+    fn unstitch_default_divisors() {
+        let atlas = round_trip(
+            r#"{
+                "sources": [
+                    {
+                        "type": "minecraft:unstitch",
+                        "resource": "minecraft:gui/icons",
+                        "regions": [
+                            { "sprite": "hud/heart/full", "x": 0, "y": 0, "width": 1, "height": 1 }
+                        ]
+                    }
+                ]
+            }"#,
+        );
+
+        let AtlasSource::Unstitch {
+            divisor_x,
+            divisor_y,
+            ..
+        } = &atlas.sources[0]
+        else {
+            panic!("Expected an unstitch source: {:?}", atlas.sources[0]);
+        };
+
+        assert_eq!((*divisor_x, *divisor_y), (1.0, 1.0));
+    }
+
+    #[test]
+    fn filter_matches() {
+        let atlas = round_trip(
+            r#"{
+                "sources": [
+                    { "type": "filter", "pattern": { "namespace": "quark", "path": "block/.*_debug" } }
+                ]
+            }"#,
+        );
+
+        let AtlasSource::Filter { pattern } = &atlas.sources[0] else {
+            panic!("Expected a filter source: {:?}", atlas.sources[0]);
+        };
+
+        assert!(pattern.matches(&Identifier::new("quark", "block/stone_debug")));
+        assert!(!pattern.matches(&Identifier::new("quark", "block/stone")));
+        assert!(!pattern.matches(&Identifier::new("quark", "block/stone_debug_old")));
+        assert!(!pattern.matches(&Identifier::new("quark_legacy", "block/stone_debug")));
+        assert_eq!(
+            serde_json::to_value(&atlas).unwrap()["sources"][0]["pattern"]["path"],
+            "block/.*_debug"
+        );
+    }
+}
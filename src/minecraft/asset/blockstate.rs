@@ -19,6 +19,31 @@ pub enum Blockstate {
     Multipart(Vec<BlockstateMultipart>),
 }
 
+impl Blockstate {
+    /// Every model referenced by the blockstate.
+    pub fn models(&self) -> Vec<&Identifier> {
+        self.states()
+            .into_iter()
+            .flat_map(ModelState::models)
+            .collect()
+    }
+
+    /// Every variant, or the model state applied by every multipart case.
+    pub fn states(&self) -> Vec<&ModelState> {
+        match self {
+            Self::Variants(variants) => variants.values().collect(),
+            Self::Multipart(parts) => parts.iter().map(|part| &part.apply).collect(),
+        }
+    }
+
+    pub fn states_mut(&mut self) -> Vec<&mut ModelState> {
+        match self {
+            Self::Variants(variants) => variants.values_mut().collect(),
+            Self::Multipart(parts) => parts.iter_mut().map(|part| &mut part.apply).collect(),
+        }
+    }
+}
+
 impl Asset for Blockstate {
     fn get_type() -> AssetType {
         AssetType::Blockstate
@@ -41,21 +66,63 @@ pub enum ModelState {
     Weighted(Vec<WeightedState>),
 }
 
+impl ModelState {
+    pub fn models(&self) -> Vec<&Identifier> {
+        match self {
+            Self::Single { model, .. } => vec![model],
+            Self::Weighted(states) => states.iter().map(|state| &state.model).collect(),
+        }
+    }
+
+    pub fn models_mut(&mut self) -> Vec<&mut Identifier> {
+        match self {
+            Self::Single { model, .. } => vec![model],
+            Self::Weighted(states) => states.iter_mut().map(|state| &mut state.model).collect(),
+        }
+    }
+
+    pub fn set_uvlock(&mut self, value: bool) {
+        match self {
+            Self::Single { uvlock, .. } => *uvlock = value,
+            Self::Weighted(states) => states.iter_mut().for_each(|state| state.uvlock = value),
+        }
+    }
+
+    /// Every state in weighted form; a lone state gets the default weight.
+    pub fn into_weighted(self) -> Vec<WeightedState> {
+        match self {
+            Self::Single {
+                model,
+                x,
+                y,
+                uvlock,
+            } => vec![WeightedState {
+                model,
+                x,
+                y,
+                uvlock,
+                weight: WeightedState::default_weight(),
+            }],
+            Self::Weighted(states) => states,
+        }
+    }
+}
+
 #[skip_serializing_none]
-#[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 pub struct WeightedState {
-    model: Identifier,
+    pub model: Identifier,
     #[serde(default, skip_serializing_if = "StateRotation::is_default")]
-    x: StateRotation,
+    pub x: StateRotation,
     #[serde(default, skip_serializing_if = "StateRotation::is_default")]
-    y: StateRotation,
+    pub y: StateRotation,
     #[serde(default, skip_serializing_if = "is_false")]
-    uvlock: bool,
+    pub uvlock: bool,
     #[serde(
         default = "WeightedState::default_weight",
         skip_serializing_if = "WeightedState::is_weight_default"
     )]
-    weight: u8,
+    pub weight: u8,
 }
 
 impl WeightedState {
@@ -73,8 +140,8 @@ impl WeightedState {
 #[skip_serializing_none]
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
 pub struct BlockstateMultipart {
-    when: Option<MultipartCondition>,
-    apply: ModelState,
+    pub when: Option<MultipartCondition>,
+    pub apply: ModelState,
 }
 
 #[derive(Debug, PartialEq, Eq, Deserialize, Serialize)]
@@ -0,0 +1,194 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_with::{serde_as, skip_serializing_none, OneOrMany};
+
+use super::{
+    types::identifier::{AssetType, Identifier},
+    Asset,
+};
+
+#[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Font {
+    pub providers: Vec<FontProvider>,
+}
+
+impl Asset for Font {
+    fn get_type() -> AssetType {
+        AssetType::Font
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct FontProvider {
+    #[serde(flatten)]
+    pub provider: FontProviderType,
+    pub filter: Option<IndexMap<String, bool>>,
+}
+
+impl From<FontProviderType> for FontProvider {
+    fn from(value: FontProviderType) -> Self {
+        Self {
+            provider: value,
+            filter: None,
+        }
+    }
+}
+
+#[serde_as]
+#[skip_serializing_none]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FontProviderType {
+    #[serde(alias = "minecraft:bitmap")]
+    Bitmap {
+        file: Identifier,
+        height: Option<i32>,
+        ascent: i32,
+        chars: Vec<String>,
+    },
+    #[serde(alias = "minecraft:ttf")]
+    Ttf {
+        file: Identifier,
+        shift: Option<[f32; 2]>,
+        size: Option<f32>,
+        oversample: Option<f32>,
+        #[serde_as(as = "OneOrMany<_>")]
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        skip: Vec<String>,
+    },
+    #[serde(alias = "minecraft:space")]
+    Space { advances: IndexMap<char, f32> },
+    #[serde(alias = "minecraft:reference")]
+    Reference { id: Identifier },
+    #[serde(alias = "minecraft:unihex")]
+    Unihex {
+        hex_file: Identifier,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        size_overrides: Vec<UnihexSizeOverride>,
+    },
+    #[serde(alias = "minecraft:legacy_unicode")]
+    LegacyUnicode { sizes: Identifier, template: String },
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct UnihexSizeOverride {
+    pub from: char,
+    pub to: char,
+    pub left: i32,
+    pub right: i32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    macro_rules! deserialize_test {
+        ($name:ident, $raw:literal, $expected: expr $(,)?) => {
+            #[test]
+            fn $name() {
+                let parsed: FontProvider = serde_json::from_str($raw).unwrap();
+                assert_eq!(parsed, $expected);
+            }
+        };
+    }
+
+    deserialize_test!(
+        deserialize_bitmap,
+        r#"{
+            "type": "bitmap",
+            "file": "minecraft:font/ascii.png",
+            "ascent": 7,
+            "chars": ["\u0000\u0001", "ab"]
+        }"#,
+        FontProvider::from(FontProviderType::Bitmap {
+            file: Identifier::minecraft("font/ascii.png"),
+            height: None,
+            ascent: 7,
+            chars: vec!["\u{0}\u{1}".to_string(), "ab".to_string()],
+        }),
+    );
+
+    deserialize_test!(
+        deserialize_ttf_filtered,
+        r#"{
+            "type": "ttf",
+            "file": "minecraft:default.ttf",
+            "shift": [0, 1],
+            "skip": "abc",
+            "filter": { "uniform": false }
+        }"#,
+        FontProvider {
+            provider: FontProviderType::Ttf {
+                file: Identifier::minecraft("default.ttf"),
+                shift: Some([0.0, 1.0]),
+                size: None,
+                oversample: None,
+                skip: vec!["abc".to_string()],
+            },
+            filter: Some(IndexMap::from([("uniform".to_string(), false)])),
+        },
+    );
+
+    deserialize_test!(
+        deserialize_space,
+        r#"{ "type": "minecraft:space", "advances": { " ": 4, "\u200c": 0 } }"#,
+        FontProvider::from(FontProviderType::Space {
+            advances: IndexMap::from([(' ', 4.0), ('\u{200c}', 0.0)]),
+        }),
+    );
+
+    deserialize_test!(
+        deserialize_reference,
+        r#"{ "type": "reference", "id": "minecraft:include/space" }"#,
+        FontProvider::from(FontProviderType::Reference {
+            id: Identifier::minecraft("include/space"),
+        }),
+    );
+
+    deserialize_test!(
+        deserialize_unihex,
+        r#"{
+            "type": "unihex",
+            "hex_file": "minecraft:font/unifont.zip",
+            "size_overrides": [{ "from": "\u3001", "to": "\u30ff", "left": 0, "right": 15 }]
+        }"#,
+        FontProvider::from(FontProviderType::Unihex {
+            hex_file: Identifier::minecraft("font/unifont.zip"),
+            size_overrides: vec![UnihexSizeOverride {
+                from: '\u{3001}',
+                to: '\u{30ff}',
+                left: 0,
+                right: 15,
+            }],
+        }),
+    );
+
+    deserialize_test!(
+        deserialize_legacy_unicode,
+        r#"{
+            "type": "legacy_unicode",
+            "sizes": "minecraft:font/glyph_sizes.bin",
+            "template": "minecraft:font/unicode_page_%s.png"
+        }"#,
+        FontProvider::from(FontProviderType::LegacyUnicode {
+            sizes: Identifier::minecraft("font/glyph_sizes.bin"),
+            template: "minecraft:font/unicode_page_%s.png".to_string(),
+        }),
+    );
+
+    #[test]
+    fn serialize_bitmap() {
+        let provider = FontProvider::from(FontProviderType::Bitmap {
+            file: Identifier::new("quark", "font/icons.png"),
+            height: Some(9),
+            ascent: 8,
+            chars: vec!["ab".to_string()],
+        });
+
+        assert_eq!(
+            serde_json::to_string(&provider).unwrap(),
+            r#"{"type":"bitmap","file":"quark:font/icons.png","height":9,"ascent":8,"chars":["ab"]}"#
+        );
+    }
+}
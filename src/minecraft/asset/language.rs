@@ -0,0 +1,64 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::MergeableAsset;
+
+use super::{types::identifier::AssetType, Asset};
+
+pub const DEFAULT_LANGUAGE: &str = "en_us";
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct Language(pub IndexMap<String, String>);
+
+impl Language {
+    pub fn missing_keys<'a>(&'a self, reference: &'a Self) -> impl Iterator<Item = &'a String> {
+        reference.0.keys().filter(|key| !self.0.contains_key(*key))
+    }
+}
+
+impl MergeableAsset for Language {
+    fn merge(&mut self, other: Self) {
+        self.0.extend(other.0);
+    }
+}
+
+impl Asset for Language {
+    fn get_type() -> AssetType {
+        AssetType::Language
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> Language {
+        serde_json::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn merge_overrides_keys() {
+        let mut base = parse(r#"{ "block.quark.lamp": "Lamp", "item.quark.gem": "Gem" }"#);
+        base.merge(parse(
+            r#"{ "item.quark.gem": "Jewel", "item.quark.rod": "Rod" }"#,
+        ));
+
+        let expected = parse(
+            r#"{ "block.quark.lamp": "Lamp", "item.quark.gem": "Jewel", "item.quark.rod": "Rod" }"#,
+        );
+
+        assert_eq!(base, expected);
+    }
+
+    #[test]
+    fn missing_keys() {
+        let reference = parse(r#"{ "block.quark.lamp": "Lamp", "item.quark.gem": "Gem" }"#);
+        let language = parse(r#"{ "block.quark.lamp": "Lampe" }"#);
+
+        assert_eq!(
+            language.missing_keys(&reference).collect::<Vec<_>>(),
+            vec!["item.quark.gem"]
+        );
+    }
+}
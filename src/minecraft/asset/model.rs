@@ -1,12 +1,14 @@
 pub mod flip;
 pub mod rotate;
+pub mod scale;
 pub mod translate;
+pub mod uv;
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_with::skip_serializing_none;
 
-use crate::minecraft::serialize::*;
+use crate::{compile::modifier::zfighting::Direction, minecraft::serialize::*};
 
 use super::{
     types::{
@@ -93,6 +95,14 @@ impl Model {
     }
 }
 
+#[cfg(test)]
+impl Model {
+    /// A model with nothing but the given elements.
+    pub fn from_elements(elements: serde_json::Value) -> Self {
+        serde_json::from_value(serde_json::json!({ "elements": elements })).unwrap()
+    }
+}
+
 impl Asset for Model {
     fn get_type() -> AssetType {
         AssetType::Model
@@ -314,6 +324,28 @@ pub struct ElementFaces {
 }
 
 impl ElementFaces {
+    pub fn get(&self, direction: Direction) -> Option<&ElementFace> {
+        match direction {
+            Direction::North => self.north.as_ref(),
+            Direction::East => self.east.as_ref(),
+            Direction::South => self.south.as_ref(),
+            Direction::West => self.west.as_ref(),
+            Direction::Up => self.up.as_ref(),
+            Direction::Down => self.down.as_ref(),
+        }
+    }
+
+    pub fn get_mut(&mut self, direction: Direction) -> &mut Option<ElementFace> {
+        match direction {
+            Direction::North => &mut self.north,
+            Direction::East => &mut self.east,
+            Direction::South => &mut self.south,
+            Direction::West => &mut self.west,
+            Direction::Up => &mut self.up,
+            Direction::Down => &mut self.down,
+        }
+    }
+
     pub fn set_cullface(&mut self, value: &CullDirection) {
         for face in self.into_iter().flatten() {
             face.cullface = *value;
@@ -377,22 +409,60 @@ impl<'a> IntoIterator for &'a mut ElementFaces {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 pub struct ElementRotation {
-    origin: Vec3,
-    axis: Axis,
-    angle: f32,
+    pub origin: Vec3,
+    pub axis: Axis,
+    #[serde(serialize_with = "serialize_float_int")]
+    pub angle: f32,
     #[serde(default, skip_serializing_if = "is_false")]
-    rescale: bool,
+    pub rescale: bool,
 }
 
 impl ElementRotation {
+    /// Older formats only accept these angles.
+    pub const LEGACY_ANGLES: [f32; 5] = [-45.0, -22.5, 0.0, 22.5, 45.0];
+    /// The largest angle the game accepts in any format.
+    pub const MAX_ANGLE: f32 = 45.0;
+    /// The first pack format that accepts any angle up to [`Self::MAX_ANGLE`].
+    pub const FREE_ANGLE_FORMAT: u8 = 63;
+
+    /// Whether the game accepts the angle in the pack format.
+    pub fn is_angle_allowed(angle: f32, pack_format: u8) -> bool {
+        if pack_format >= Self::FREE_ANGLE_FORMAT {
+            angle.abs() <= Self::MAX_ANGLE
+        } else {
+            Self::LEGACY_ANGLES.contains(&angle)
+        }
+    }
+
     pub fn is_zero(&self) -> bool {
         self.angle == 0.0
     }
+
+    /// Where the game places a point of the element once this rotation is applied.
+    pub fn transform(&self, point: Vec3) -> Vec3 {
+        let (sin, cos) = self.angle.to_radians().sin_cos();
+        let scale = if self.rescale { 1.0 / cos.abs() } else { 1.0 };
+        let (x, y, z) = (point - self.origin).into();
+
+        let (x, y, z) = match self.axis {
+            Axis::X => (x, (y * cos - z * sin) * scale, (y * sin + z * cos) * scale),
+            Axis::Y => ((x * cos + z * sin) * scale, y, (z * cos - x * sin) * scale),
+            Axis::Z => ((x * cos - y * sin) * scale, (x * sin + y * cos) * scale, z),
+        };
+
+        Vec3::new(x, y, z) + self.origin
+    }
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+impl ModelElement {
+    pub fn is_axis_aligned(&self) -> bool {
+        self.rotation.as_ref().is_none_or(ElementRotation::is_zero)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Axis {
     X,
@@ -432,39 +502,62 @@ impl ElementFace {
 #[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ItemDisplayPositions {
-    thirdperson_righthand: Option<ItemDisplay>,
-    thirdperson_lefthand: Option<ItemDisplay>,
-    firstperson_righthand: Option<ItemDisplay>,
-    firstperson_lefthand: Option<ItemDisplay>,
-    gui: Option<ItemDisplay>,
-    head: Option<ItemDisplay>,
-    ground: Option<ItemDisplay>,
-    fixed: Option<ItemDisplay>,
+    pub thirdperson_righthand: Option<ItemDisplay>,
+    pub thirdperson_lefthand: Option<ItemDisplay>,
+    pub firstperson_righthand: Option<ItemDisplay>,
+    pub firstperson_lefthand: Option<ItemDisplay>,
+    pub gui: Option<ItemDisplay>,
+    pub head: Option<ItemDisplay>,
+    pub ground: Option<ItemDisplay>,
+    pub fixed: Option<ItemDisplay>,
+}
+
+impl<'a> IntoIterator for &'a mut ItemDisplayPositions {
+    type Item = Option<&'a mut ItemDisplay>;
+    type IntoIter = std::array::IntoIter<Self::Item, 8>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        [
+            self.thirdperson_righthand.as_mut(),
+            self.thirdperson_lefthand.as_mut(),
+            self.firstperson_righthand.as_mut(),
+            self.firstperson_lefthand.as_mut(),
+            self.gui.as_mut(),
+            self.head.as_mut(),
+            self.ground.as_mut(),
+            self.fixed.as_mut(),
+        ]
+        .into_iter()
+    }
 }
 
 #[skip_serializing_none]
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ItemDisplay {
-    rotation: Option<Vec3>,
-    translation: Option<Vec3>,
-    scale: Option<Vec3>,
+    pub rotation: Option<Vec3>,
+    pub translation: Option<Vec3>,
+    pub scale: Option<Vec3>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum GuiLightDirection {
     Front,
+    #[default]
     Side,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ItemModelOverride {
-    predicate: IndexMap<String, i32>,
-    model: Identifier,
+    pub predicate: IndexMap<String, i32>,
+    pub model: Identifier,
 }
 
 #[derive(Debug, Default, Deserialize, Clone)]
+#[serde(default)]
 pub struct ModelSettings {
     #[serde(rename = "virtual")]
     is_virtual: bool,
+    /// Inlines the parents into this model before modifiers run.
+    pub flatten: bool,
 }
@@ -1,6 +1,30 @@
-use crate::minecraft::asset::types::rotation::StateRotation;
-
-use super::{CullDirection, ElementFaces, Model, ModelElement};
+use anyhow::bail;
+
+use crate::{
+    compile::modifier::zfighting::Direction,
+    minecraft::asset::types::{rotation::StateRotation, vec::Vec3},
+};
+
+use super::{Axis, CullDirection, ElementFaces, ElementRotation, Model, ModelElement};
+
+/// Quarter turns that faces with a UV pick up from one x quarter rotation, by their new side.
+const X_QUARTER_FACE_TURNS: [(Direction, u8); 4] = [
+    (Direction::North, 2),
+    (Direction::East, 3),
+    (Direction::West, 1),
+    (Direction::Up, 2),
+];
+/// Quarter turns that faces with a UV pick up from one y quarter rotation, by their new side.
+const Y_QUARTER_FACE_TURNS: [(Direction, u8); 2] = [(Direction::Up, 3), (Direction::Down, 1)];
+/// Quarter turns that faces with a UV pick up from one z quarter rotation, by their new side.
+const Z_QUARTER_FACE_TURNS: [(Direction, u8); 6] = [
+    (Direction::North, 1),
+    (Direction::East, 3),
+    (Direction::South, 3),
+    (Direction::West, 3),
+    (Direction::Up, 3),
+    (Direction::Down, 3),
+];
 
 pub trait Rotatable {
     fn rotate_x(&mut self, amount: &StateRotation) {
@@ -15,9 +39,25 @@ pub trait Rotatable {
         }
     }
 
+    fn rotate_z(&mut self, amount: &StateRotation) {
+        for _ in 0..amount.quarters() {
+            self.rotate_z_quarter()
+        }
+    }
+
+    fn rotate_axis(&mut self, axis: &Axis, amount: &StateRotation) {
+        match axis {
+            Axis::X => self.rotate_x(amount),
+            Axis::Y => self.rotate_y(amount),
+            Axis::Z => self.rotate_z(amount),
+        }
+    }
+
     fn rotate_x_quarter(&mut self);
 
     fn rotate_y_quarter(&mut self);
+
+    fn rotate_z_quarter(&mut self);
 }
 
 impl Rotatable for Model {
@@ -32,6 +72,189 @@ impl Rotatable for Model {
             element.rotate_y_quarter();
         }
     }
+
+    fn rotate_z_quarter(&mut self) {
+        for element in &mut self.elements {
+            element.rotate_z_quarter();
+        }
+    }
+}
+
+impl Model {
+    /// Rotates the model while textures keep their orientation in the world, like blockstate uvlock.
+    pub fn rotate_uvlock(&mut self, x: &StateRotation, y: &StateRotation, z: &StateRotation) {
+        for _ in 0..x.quarters() {
+            self.rotate_x_quarter();
+            self.lock_uv(&X_QUARTER_FACE_TURNS);
+        }
+
+        for _ in 0..y.quarters() {
+            self.rotate_y_quarter();
+            self.lock_uv(&Y_QUARTER_FACE_TURNS);
+        }
+
+        for _ in 0..z.quarters() {
+            self.rotate_z_quarter();
+            self.lock_uv(&Z_QUARTER_FACE_TURNS);
+        }
+    }
+
+    /// Rotates the model by any angle around `origin`.
+    ///
+    /// Whole quarter turns are applied to the geometry; what remains is written as the rotation
+    /// of each element. Elements that are rotated already must share the axis and origin, and
+    /// the combined angle may not exceed what the game allows.
+    pub fn rotate_free(
+        &mut self,
+        axis: &Axis,
+        angle: f32,
+        origin: &Vec3,
+        rescale: bool,
+        uvlock: bool,
+    ) -> anyhow::Result<()> {
+        let quarters = (angle / 90.0).round();
+        let remainder = angle - quarters * 90.0;
+        let quarters = StateRotation::from_quarters((quarters as i32).rem_euclid(4) as u8);
+
+        if uvlock {
+            let none = StateRotation::default();
+
+            match axis {
+                Axis::X => self.rotate_uvlock(&quarters, &none, &none),
+                Axis::Y => self.rotate_uvlock(&none, &quarters, &none),
+                Axis::Z => self.rotate_uvlock(&none, &none, &quarters),
+            }
+        } else {
+            self.rotate_axis(axis, &quarters);
+        }
+
+        // Quarter turns happen around the center of the block; move the origin back in place.
+        let mut rotated_origin = *origin;
+        rotated_origin.rotate_axis(axis, &quarters);
+
+        let offset = *origin - rotated_origin;
+
+        for element in &mut self.elements {
+            element.from += offset;
+            element.to += offset;
+
+            if let Some(rotation) = &mut element.rotation {
+                rotation.origin += offset;
+            }
+        }
+
+        if remainder == 0.0 {
+            return Ok(());
+        }
+
+        let rotation = ElementRotation {
+            origin: *origin,
+            axis: *axis,
+            angle: remainder,
+            rescale,
+        };
+
+        for element in &mut self.elements {
+            element.add_rotation(&rotation)?;
+        }
+
+        Ok(())
+    }
+
+    fn lock_uv(&mut self, face_turns: &[(Direction, u8)]) {
+        for element in &mut self.elements {
+            for (direction, quarters) in face_turns {
+                if let Some(face) = element.faces.get_mut(*direction) {
+                    face.lock_uv(*quarters);
+                }
+            }
+        }
+    }
+}
+
+impl ModelElement {
+    fn add_rotation(&mut self, rotation: &ElementRotation) -> anyhow::Result<()> {
+        let Some(existing) = self
+            .rotation
+            .as_mut()
+            .filter(|existing| !existing.is_zero())
+        else {
+            self.rotation = Some(rotation.clone());
+            return Ok(());
+        };
+
+        if existing.axis != rotation.axis || existing.origin != rotation.origin {
+            bail!("Element is already rotated around a different axis or origin");
+        }
+
+        existing.angle += rotation.angle;
+        existing.rescale |= rotation.rescale;
+
+        if existing.angle.abs() > ElementRotation::MAX_ANGLE {
+            bail!(
+                "Element rotation of {} degrees exceeds {} degrees",
+                existing.angle,
+                ElementRotation::MAX_ANGLE
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Rotatable for Vec3 {
+    fn rotate_x_quarter(&mut self) {
+        (self.y, self.z) = (16.0 - self.z, self.y);
+    }
+
+    fn rotate_y_quarter(&mut self) {
+        (self.x, self.z) = (self.z, 16.0 - self.x);
+    }
+
+    fn rotate_z_quarter(&mut self) {
+        (self.x, self.y) = (16.0 - self.y, self.x);
+    }
+}
+
+impl Rotatable for ElementRotation {
+    fn rotate_x_quarter(&mut self) {
+        self.origin.rotate_x_quarter();
+
+        match self.axis {
+            Axis::X => (),
+            Axis::Y => self.axis = Axis::Z,
+            Axis::Z => {
+                self.axis = Axis::Y;
+                self.angle = -self.angle;
+            }
+        }
+    }
+
+    fn rotate_y_quarter(&mut self) {
+        self.origin.rotate_y_quarter();
+
+        match self.axis {
+            Axis::X => {
+                self.axis = Axis::Z;
+                self.angle = -self.angle;
+            }
+            Axis::Y => (),
+            Axis::Z => self.axis = Axis::X,
+        }
+    }
+
+    fn rotate_z_quarter(&mut self) {
+        self.origin.rotate_z_quarter();
+
+        match self.axis {
+            Axis::X => self.axis = Axis::Y,
+            Axis::Y => {
+                self.axis = Axis::X;
+                self.angle = -self.angle;
+            }
+            Axis::Z => (),
+        }
+    }
 }
 
 impl Rotatable for CullDirection {
@@ -54,12 +277,26 @@ impl Rotatable for CullDirection {
             _ => (),
         }
     }
+
+    fn rotate_z_quarter(&mut self) {
+        match self {
+            Self::East => *self = Self::Up,
+            Self::Up => *self = Self::West,
+            Self::West => *self = Self::Down,
+            Self::Down => *self = Self::East,
+            _ => (),
+        }
+    }
 }
 
 impl Rotatable for ModelElement {
     fn rotate_x_quarter(&mut self) {
         self.faces.rotate_x_quarter();
 
+        if let Some(rotation) = &mut self.rotation {
+            rotation.rotate_x_quarter();
+        }
+
         let (_, from_y, from_z) = (&mut self.from).into();
         let (_, to_y, to_z) = (&mut self.to).into();
 
@@ -78,6 +315,10 @@ impl Rotatable for ModelElement {
     fn rotate_y_quarter(&mut self) {
         self.faces.rotate_y_quarter();
 
+        if let Some(rotation) = &mut self.rotation {
+            rotation.rotate_y_quarter();
+        }
+
         let (from_x, _, from_z) = (&mut self.from).into();
         let (to_x, _, to_z) = (&mut self.to).into();
 
@@ -92,6 +333,17 @@ impl Rotatable for ModelElement {
         *to_x = corner_x + length_x;
         *to_z = corner_z + length_z;
     }
+
+    fn rotate_z_quarter(&mut self) {
+        self.faces.rotate_z_quarter();
+
+        if let Some(rotation) = &mut self.rotation {
+            rotation.rotate_z_quarter();
+        }
+
+        (self.from.x, self.to.x, self.from.y, self.to.y) =
+            (16.0 - self.to.y, 16.0 - self.from.y, self.from.x, self.to.x);
+    }
 }
 
 impl Rotatable for ElementFaces {
@@ -177,6 +429,37 @@ impl Rotatable for ElementFaces {
             face.cullface.rotate_y_quarter();
         }
     }
+
+    fn rotate_z_quarter(&mut self) {
+        let temp = self.down.clone();
+        self.down.clone_from(&self.west);
+        self.west.clone_from(&self.up);
+        self.up.clone_from(&self.east);
+        self.east = temp;
+
+        if let Some(face) = &mut self.north {
+            if face.uv.is_some() {
+                face.rotation = face.rotation.rotate_quarter();
+            }
+            face.cullface.rotate_z_quarter();
+        }
+
+        for face in [
+            &mut self.east,
+            &mut self.south,
+            &mut self.west,
+            &mut self.up,
+            &mut self.down,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if face.uv.is_some() {
+                face.rotation = face.rotation.rotate_quarter_counter();
+            }
+            face.cullface.rotate_z_quarter();
+        }
+    }
 }
 
 impl StateRotation {
@@ -207,3 +490,102 @@ impl StateRotation {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn rotate_z_full_turn() {
+        let elements = json!([{
+            "from": [0, 2, 4],
+            "to": [6, 8, 10],
+            "rotation": { "origin": [4, 4, 4], "axis": "x", "angle": 22.5 },
+            "faces": {
+                "north": { "texture": "#side", "uv": [0, 0, 8, 8] },
+                "east": { "texture": "#side", "uv": [0, 0, 8, 8], "cullface": "east" },
+                "up": { "texture": "#top" }
+            }
+        }]);
+        let mut rotated = Model::from_elements(elements.clone());
+        rotated.rotate_z(&StateRotation::Degrees180);
+        rotated.rotate_z(&StateRotation::Degrees180);
+
+        assert_eq!(serde_json::to_value(rotated.elements).unwrap(), elements);
+    }
+
+    #[test]
+    fn rotate_z_quarter_element() {
+        let mut rotated = Model::from_elements(json!([{
+            "from": [0, 2, 4],
+            "to": [6, 8, 10],
+            "faces": { "east": { "texture": "#side", "cullface": "east" } }
+        }]));
+        rotated.rotate_z(&StateRotation::Degrees90);
+
+        assert_eq!(
+            serde_json::to_value(rotated.elements).unwrap(),
+            json!([{
+                "from": [8, 0, 4],
+                "to": [14, 6, 10],
+                "faces": { "up": { "texture": "#side", "cullface": "up" } }
+            }])
+        );
+    }
+
+    #[test]
+    fn rotate_free_angle() {
+        let mut rotated = Model::from_elements(json!([{ "from": [0, 0, 0], "to": [4, 16, 4] }]));
+        rotated
+            .rotate_free(&Axis::Y, 112.5, &Vec3::new(4.0, 0.0, 4.0), false, false)
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(rotated.elements).unwrap(),
+            json!([{
+                "from": [0, 0, 4],
+                "to": [4, 16, 8],
+                "rotation": { "origin": [4, 0, 4], "axis": "y", "angle": 22.5 }
+            }])
+        );
+    }
+
+    #[test]
+    fn rotate_free_rotated_element() {
+        let mut rotated = Model::from_elements(json!([{
+            "from": [0, 0, 0],
+            "to": [4, 16, 4],
+            "rotation": { "origin": [4, 0, 4], "axis": "y", "angle": 22.5 }
+        }]));
+        rotated
+            .rotate_free(&Axis::Y, 112.5, &Vec3::new(4.0, 0.0, 4.0), false, false)
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(rotated.elements).unwrap(),
+            json!([{
+                "from": [0, 0, 4],
+                "to": [4, 16, 8],
+                "rotation": { "origin": [4, 0, 4], "axis": "y", "angle": 45 }
+            }])
+        );
+    }
+
+    #[test]
+    fn rotate_free_conflicting_axis() {
+        let mut rotated = Model::from_elements(json!([{
+            "from": [0, 0, 0],
+            "to": [16, 16, 16],
+            "rotation": { "origin": [8, 8, 8], "axis": "x", "angle": 22.5 }
+        }]));
+
+        assert!(rotated
+            .rotate_free(&Axis::Z, 22.5, &Vec3::new(8.0, 8.0, 8.0), false, false)
+            .is_err());
+        assert!(rotated
+            .rotate_free(&Axis::X, 30.0, &Vec3::new(8.0, 8.0, 8.0), false, false)
+            .is_err());
+    }
+}
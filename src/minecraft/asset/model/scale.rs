@@ -0,0 +1,148 @@
+use anyhow::bail;
+
+use crate::{compile::modifier::zfighting::Direction, minecraft::asset::types::vec::Vec3};
+
+use super::{Axis, Model, ModelElement};
+
+const AXES: [Axis; 3] = [Axis::X, Axis::Y, Axis::Z];
+
+pub trait Scale {
+    /// Scales by `factor` along each axis, away from or towards `pivot`.
+    ///
+    /// With `scale_uv`, faces keep the size of their texels and show less or more of the
+    /// texture; otherwise the texture stretches with the face.
+    fn scale(&mut self, factor: &Vec3, pivot: &Vec3, scale_uv: bool) -> anyhow::Result<()>;
+}
+
+impl Scale for Model {
+    fn scale(&mut self, factor: &Vec3, pivot: &Vec3, scale_uv: bool) -> anyhow::Result<()> {
+        if AXES.iter().any(|axis| factor.axis(axis) <= 0.0) {
+            bail!("Scale factors must be positive: {:?}", factor);
+        }
+
+        for element in &mut self.elements {
+            element.scale(factor, pivot, scale_uv)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Scale for ModelElement {
+    fn scale(&mut self, factor: &Vec3, pivot: &Vec3, scale_uv: bool) -> anyhow::Result<()> {
+        if let Some(rotation) = self
+            .rotation
+            .as_mut()
+            .filter(|rotation| !rotation.is_zero())
+        {
+            let mut plane = AXES
+                .iter()
+                .filter(|axis| **axis != rotation.axis)
+                .map(|axis| factor.axis(axis));
+
+            // Stretching a rotated element unevenly would skew it.
+            if plane.next() != plane.next() {
+                bail!("Can't scale a rotated element differently along its rotation");
+            }
+
+            scale_point(&mut rotation.origin, factor, pivot);
+        }
+
+        let bounds = Direction::ALL.map(|direction| self.auto_uv(direction));
+
+        scale_point(&mut self.from, factor, pivot);
+        scale_point(&mut self.to, factor, pivot);
+
+        if scale_uv {
+            for (direction, bounds) in Direction::ALL.into_iter().zip(bounds) {
+                let region = self.auto_uv(direction);
+
+                if let Some(face) = self.faces.get_mut(direction) {
+                    face.crop_uv(&bounds, &region);
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn scale_point(point: &mut Vec3, factor: &Vec3, pivot: &Vec3) {
+    for axis in &AXES {
+        let value = point.axis_mut(axis);
+        *value = pivot.axis(axis) + (*value - pivot.axis(axis)) * factor.axis(axis);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn scale(elements: serde_json::Value, factor: Vec3, scale_uv: bool) -> serde_json::Value {
+        let mut model = Model::from_elements(elements);
+        model
+            .scale(&factor, &Vec3::new(8.0, 0.0, 8.0), scale_uv)
+            .unwrap();
+        serde_json::to_value(model.elements).unwrap()
+    }
+
+    #[test]
+    fn scale_element() {
+        assert_eq!(
+            scale(
+                json!([{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "rotation": { "origin": [4, 8, 8], "axis": "y", "angle": 22.5 },
+                    "faces": { "north": { "texture": "#side", "uv": [0, 0, 16, 16] } }
+                }]),
+                Vec3::new(0.5, 0.5, 0.5),
+                false
+            ),
+            json!([{
+                "from": [4, 0, 4],
+                "to": [12, 8, 12],
+                "rotation": { "origin": [6, 4, 8], "axis": "y", "angle": 22.5 },
+                "faces": { "north": { "texture": "#side", "uv": [0, 0, 16, 16] } }
+            }])
+        );
+    }
+
+    #[test]
+    fn scale_uv() {
+        assert_eq!(
+            scale(
+                json!([{
+                    "from": [0, 0, 0],
+                    "to": [16, 16, 16],
+                    "faces": { "north": { "texture": "#side", "uv": [0, 0, 16, 16] } }
+                }]),
+                Vec3::new(0.5, 1.0, 1.0),
+                true
+            ),
+            json!([{
+                "from": [4, 0, 0],
+                "to": [12, 16, 16],
+                "faces": { "north": { "texture": "#side", "uv": [4, 0, 12, 16] } }
+            }])
+        );
+    }
+
+    #[test]
+    fn scale_rotated_unevenly() {
+        let mut model = Model::from_elements(json!([{
+            "from": [0, 0, 0],
+            "to": [16, 16, 16],
+            "rotation": { "origin": [8, 8, 8], "axis": "y", "angle": 22.5 }
+        }]));
+
+        assert!(model
+            .scale(&Vec3::new(0.5, 1.0, 1.0), &Vec3::new(8.0, 8.0, 8.0), false)
+            .is_err());
+        assert!(model
+            .scale(&Vec3::new(0.5, 2.0, 0.5), &Vec3::new(8.0, 8.0, 8.0), false)
+            .is_ok());
+    }
+}
@@ -0,0 +1,231 @@
+use crate::{
+    compile::modifier::zfighting::Direction,
+    minecraft::asset::types::{rotation::StateRotation, vec::Vec4},
+};
+
+use super::{ElementFace, Model, ModelElement};
+
+pub const TEXTURE_SIZE: f32 = 16.0;
+
+impl Model {
+    /// Gives every face without a UV the one the game would derive, so it survives moving the face.
+    pub fn bake_uv(&mut self) {
+        for element in &mut self.elements {
+            element.bake_uv();
+        }
+    }
+}
+
+impl ModelElement {
+    pub fn bake_uv(&mut self) {
+        for direction in Direction::ALL {
+            let auto_uv = self.auto_uv(direction);
+
+            if let Some(face) = self.faces.get_mut(direction) {
+                face.uv.get_or_insert(auto_uv);
+            }
+        }
+    }
+
+    /// The UV the game gives a face without one, derived from the bounds of the element.
+    pub fn auto_uv(&self, direction: Direction) -> Vec4 {
+        let (from, to) = (self.from, self.to);
+
+        match direction {
+            Direction::Down => (from.x, 16.0 - to.z, to.x, 16.0 - from.z),
+            Direction::Up => (from.x, from.z, to.x, to.z),
+            Direction::North => (16.0 - to.x, 16.0 - to.y, 16.0 - from.x, 16.0 - from.y),
+            Direction::South => (from.x, 16.0 - to.y, to.x, 16.0 - from.y),
+            Direction::West => (from.z, 16.0 - to.y, to.z, 16.0 - from.y),
+            Direction::East => (16.0 - to.z, 16.0 - to.y, 16.0 - from.z, 16.0 - from.y),
+        }
+        .into()
+    }
+}
+
+impl ElementFace {
+    /// Crops the texture to the part of the face that remains after shrinking it.
+    ///
+    /// `bounds` and `region` are the auto UVs of the face before and after shrinking.
+    /// Unrotated faces without a UV already follow the bounds of their element.
+    pub fn crop_uv(&mut self, bounds: &Vec4, region: &Vec4) {
+        if self.uv.is_none() && self.rotation.is_default() {
+            return;
+        }
+
+        let width = bounds.y - bounds.w;
+        let height = bounds.z - bounds.x;
+
+        if width == 0.0 || height == 0.0 {
+            return;
+        }
+
+        // The region as fractions of the face; `s` runs along the face's u axis, `t` along v.
+        let s = ((region.w - bounds.w) / width, (region.y - bounds.w) / width);
+        let t = (
+            (region.x - bounds.x) / height,
+            (region.z - bounds.x) / height,
+        );
+        let invert = |(start, end): (f32, f32)| (1.0 - end, 1.0 - start);
+
+        // Rotating the texture turns which part of the face each part of the UV lands on.
+        let (u_range, v_range) = match self.rotation {
+            StateRotation::Degrees0 => (s, t),
+            StateRotation::Degrees90 => (t, invert(s)),
+            StateRotation::Degrees180 => (invert(s), invert(t)),
+            StateRotation::Degrees270 => (invert(t), s),
+        };
+
+        let uv = self.uv.get_or_insert_with(|| bounds.clone());
+        let lerp = |start: f32, end: f32, amount: f32| start + (end - start) * amount;
+
+        *uv = Vec4 {
+            w: lerp(uv.w, uv.y, u_range.0),
+            x: lerp(uv.x, uv.z, v_range.0),
+            y: lerp(uv.w, uv.y, u_range.1),
+            z: lerp(uv.x, uv.z, v_range.1),
+        };
+    }
+
+    /// Undoes the texture rotation a face picked up from being rotated `quarters` clockwise.
+    ///
+    /// The texture keeps its orientation while the UV turns around the center of the texture
+    /// instead, following the face like blockstate uvlock does. Faces without a UV are locked
+    /// already, since the game derives their UV from their position.
+    pub fn lock_uv(&mut self, quarters: u8) {
+        let Some(uv) = &mut self.uv else {
+            return;
+        };
+
+        self.rotation = StateRotation::from_quarters(self.rotation.quarters() + 4 - quarters % 4);
+
+        for _ in 0..quarters % 4 {
+            *uv = Vec4 {
+                w: TEXTURE_SIZE - uv.z,
+                x: uv.w,
+                y: TEXTURE_SIZE - uv.x,
+                z: uv.y,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn crop(
+        face: serde_json::Value,
+        bounds: (f32, f32, f32, f32),
+        region: (f32, f32, f32, f32),
+    ) -> serde_json::Value {
+        let mut face = serde_json::from_value::<ElementFace>(face).unwrap();
+        face.crop_uv(&bounds.into(), &region.into());
+        serde_json::to_value(face).unwrap()
+    }
+
+    #[test]
+    fn auto_uv() {
+        let element = serde_json::from_value::<ModelElement>(json!({
+            "from": [2, 0, 4],
+            "to": [6, 8, 16]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            element.auto_uv(Direction::North),
+            (10.0, 8.0, 14.0, 16.0).into()
+        );
+        assert_eq!(
+            element.auto_uv(Direction::East),
+            (0.0, 8.0, 12.0, 16.0).into()
+        );
+        assert_eq!(element.auto_uv(Direction::Up), (2.0, 4.0, 6.0, 16.0).into());
+        assert_eq!(
+            element.auto_uv(Direction::Down),
+            (2.0, 0.0, 6.0, 12.0).into()
+        );
+    }
+
+    #[test]
+    fn bake_uv() {
+        let mut element = serde_json::from_value::<ModelElement>(json!({
+            "from": [0, 0, 0],
+            "to": [8, 8, 8],
+            "faces": {
+                "north": { "texture": "#side", "uv": [0, 0, 16, 16] },
+                "up": { "texture": "#top" }
+            }
+        }))
+        .unwrap();
+        element.bake_uv();
+
+        assert_eq!(
+            serde_json::to_value(element.faces).unwrap(),
+            json!({
+                "north": { "texture": "#side", "uv": [0, 0, 16, 16] },
+                "up": { "texture": "#top", "uv": [0, 0, 8, 8] }
+            })
+        );
+    }
+
+    #[test]
+    fn lock_uv() {
+        let mut face = serde_json::from_value::<ElementFace>(
+            json!({ "texture": "#top", "uv": [0, 0, 8, 4], "rotation": 90 }),
+        )
+        .unwrap();
+        face.lock_uv(1);
+
+        assert_eq!(
+            serde_json::to_value(face).unwrap(),
+            json!({ "texture": "#top", "uv": [12, 0, 16, 8] })
+        );
+    }
+
+    #[test]
+    fn crop_auto_uv() {
+        assert_eq!(
+            crop(
+                json!({ "texture": "#all" }),
+                (0.0, 0.0, 16.0, 16.0),
+                (0.0, 0.0, 8.0, 16.0)
+            ),
+            json!({ "texture": "#all" })
+        );
+    }
+
+    #[test]
+    fn crop_uv() {
+        assert_eq!(
+            crop(
+                json!({ "texture": "#all", "uv": [16, 0, 0, 8] }),
+                (0.0, 0.0, 16.0, 16.0),
+                (0.0, 0.0, 8.0, 16.0)
+            ),
+            json!({ "texture": "#all", "uv": [16, 0, 8, 8] })
+        );
+    }
+
+    #[test]
+    fn crop_rotated_uv() {
+        assert_eq!(
+            crop(
+                json!({ "texture": "#all", "rotation": 90 }),
+                (0.0, 0.0, 16.0, 16.0),
+                (0.0, 0.0, 8.0, 16.0)
+            ),
+            json!({ "texture": "#all", "uv": [0, 8, 16, 16], "rotation": 90 })
+        );
+        assert_eq!(
+            crop(
+                json!({ "texture": "#all", "uv": [0, 0, 16, 16], "rotation": 270 }),
+                (0.0, 0.0, 16.0, 16.0),
+                (0.0, 0.0, 16.0, 4.0)
+            ),
+            json!({ "texture": "#all", "uv": [12, 0, 16, 16], "rotation": 270 })
+        );
+    }
+}
@@ -0,0 +1,263 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_with::skip_serializing_none;
+
+use crate::{asset::MergeableAsset, minecraft::serialize::*};
+
+use super::{
+    types::identifier::{AssetType, Identifier},
+    Asset,
+};
+
+#[derive(Debug, Default, PartialEq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct SoundDefinitions(pub IndexMap<String, SoundEvent>);
+
+impl SoundDefinitions {
+    pub fn sound_files(&self) -> impl Iterator<Item = (&String, &Identifier)> {
+        self.0.iter().flat_map(|(event_name, event)| {
+            event
+                .sounds
+                .iter()
+                .filter_map(SoundEntry::file)
+                .map(move |file| (event_name, file))
+        })
+    }
+}
+
+impl MergeableAsset for SoundDefinitions {
+    // Events marked with `replace` overwrite the existing event entirely.
+    fn merge(&mut self, other: Self) {
+        for (event_name, event) in other.0 {
+            if let Some(existing_event) = self.0.get_mut(&event_name) {
+                existing_event.merge(event);
+            } else {
+                self.0.insert(event_name, event);
+            }
+        }
+    }
+}
+
+impl Asset for SoundDefinitions {
+    fn get_type() -> AssetType {
+        AssetType::SoundDefinitions
+    }
+}
+
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct SoundEvent {
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub replace: bool,
+    pub subtitle: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sounds: Vec<SoundEntry>,
+}
+
+impl SoundEvent {
+    fn merge(&mut self, mut other: Self) {
+        if other.replace {
+            *self = other;
+        } else {
+            if other.subtitle.is_some() {
+                self.subtitle = other.subtitle;
+            }
+
+            self.sounds.append(&mut other.sounds);
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum SoundEntry {
+    File(Identifier),
+    Detailed(SoundEntryDetailed),
+}
+
+impl SoundEntry {
+    /// The `.ogg` file this entry points at; `None` when it references another event.
+    pub fn file(&self) -> Option<&Identifier> {
+        match self {
+            Self::File(id) => Some(id),
+            Self::Detailed(entry) => match entry.sound_type {
+                SoundEntryType::File => Some(&entry.name),
+                SoundEntryType::Event => None,
+            },
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Deserialize, Serialize)]
+pub struct SoundEntryDetailed {
+    pub name: Identifier,
+    #[serde(
+        default = "SoundEntryDetailed::default_one",
+        skip_serializing_if = "SoundEntryDetailed::is_one"
+    )]
+    pub volume: f32,
+    #[serde(
+        default = "SoundEntryDetailed::default_one",
+        skip_serializing_if = "SoundEntryDetailed::is_one"
+    )]
+    pub pitch: f32,
+    #[serde(
+        default = "SoundEntryDetailed::default_weight",
+        skip_serializing_if = "SoundEntryDetailed::is_weight_default"
+    )]
+    pub weight: u32,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub stream: bool,
+    #[serde(
+        default = "SoundEntryDetailed::default_attenuation_distance",
+        skip_serializing_if = "SoundEntryDetailed::is_attenuation_distance_default"
+    )]
+    pub attenuation_distance: u32,
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub preload: bool,
+    #[serde(
+        default,
+        rename = "type",
+        skip_serializing_if = "SoundEntryType::is_default"
+    )]
+    pub sound_type: SoundEntryType,
+}
+
+impl SoundEntryDetailed {
+    #[inline]
+    fn default_one() -> f32 {
+        1.0
+    }
+
+    #[inline]
+    fn is_one(value: &f32) -> bool {
+        *value == 1.0
+    }
+
+    #[inline]
+    fn default_weight() -> u32 {
+        1
+    }
+
+    #[inline]
+    fn is_weight_default(value: &u32) -> bool {
+        *value == Self::default_weight()
+    }
+
+    #[inline]
+    fn default_attenuation_distance() -> u32 {
+        16
+    }
+
+    #[inline]
+    fn is_attenuation_distance_default(value: &u32) -> bool {
+        *value == Self::default_attenuation_distance()
+    }
+}
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SoundEntryType {
+    #[default]
+    File,
+    Event,
+}
+
+impl SoundEntryType {
+    fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(raw: &str) -> SoundDefinitions {
+        serde_json::from_str(raw).unwrap()
+    }
+
+    #[test]
+    fn deserialize_detailed() {
+        let parsed = parse(
+            r#"{
+                "ambient.cave": {
+                    "subtitle": "subtitles.ambient.cave",
+                    "sounds": [
+                        "ambient/cave/cave1",
+                        {
+                            "name": "ambient/cave/cave2",
+                            "volume": 0.5,
+                            "weight": 3,
+                            "stream": true
+                        },
+                        {
+                            "name": "block.note_block.harp",
+                            "type": "event"
+                        }
+                    ]
+                }
+            }"#,
+        );
+
+        let event = &parsed.0["ambient.cave"];
+        assert_eq!(event.subtitle.as_deref(), Some("subtitles.ambient.cave"));
+        assert_eq!(
+            event.sounds[0],
+            SoundEntry::File(Identifier::minecraft("ambient/cave/cave1"))
+        );
+        assert_eq!(
+            event.sounds[1],
+            SoundEntry::Detailed(SoundEntryDetailed {
+                name: Identifier::minecraft("ambient/cave/cave2"),
+                volume: 0.5,
+                pitch: 1.0,
+                weight: 3,
+                stream: true,
+                attenuation_distance: 16,
+                preload: false,
+                sound_type: SoundEntryType::File,
+            })
+        );
+        assert_eq!(event.sounds[2].file(), None);
+    }
+
+    #[test]
+    fn merge_appends_sounds() {
+        let mut base = parse(r#"{ "ambient.cave": { "sounds": ["ambient/cave/cave1"] } }"#);
+        base.merge(parse(
+            r#"{
+                "ambient.cave": { "sounds": ["custom:ambient/drip"] },
+                "ambient.wind": { "sounds": ["custom:ambient/wind"] }
+            }"#,
+        ));
+
+        let files = base.sound_files().map(|(_, id)| id).collect::<Vec<_>>();
+        assert_eq!(
+            files,
+            vec![
+                &Identifier::minecraft("ambient/cave/cave1"),
+                &Identifier::new("custom", "ambient/drip"),
+                &Identifier::new("custom", "ambient/wind"),
+            ]
+        );
+    }
+
+    #[test]
+    fn merge_replace() {
+        let mut base =
+            parse(r#"{ "ambient.cave": { "subtitle": "old", "sounds": ["ambient/cave/cave1"] } }"#);
+        base.merge(parse(
+            r#"{ "ambient.cave": { "replace": true, "sounds": ["custom:ambient/drip"] } }"#,
+        ));
+
+        assert_eq!(
+            base.0["ambient.cave"],
+            SoundEvent {
+                replace: true,
+                subtitle: None,
+                sounds: vec![SoundEntry::File(Identifier::new("custom", "ambient/drip"))],
+            }
+        );
+    }
+}
@@ -1,11 +1,16 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use serde_with::skip_serializing_none;
 
 use super::{types::identifier::AssetType, Asset};
 
+#[skip_serializing_none]
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TextureMeta {
-    animation: TextureAnimation,
+    pub animation: Option<TextureAnimation>,
+    /// Sections mcpacker doesn't read, such as `texture` and `gui`, kept as they are.
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
 }
 
 impl Asset for TextureMeta {
@@ -17,16 +22,91 @@ impl Asset for TextureMeta {
 #[skip_serializing_none]
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct TextureAnimation {
-    interpolate: Option<bool>,
-    width: Option<u32>,
-    height: Option<u32>,
-    frametime: Option<u32>,
-    frames: Option<AnimationFrames>,
+    pub interpolate: Option<bool>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub frametime: Option<u32>,
+    pub frames: Option<Vec<AnimationFrame>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl TextureAnimation {
+    /// The size of a single frame; unspecified sides fall back the same way as in game.
+    pub fn frame_size(&self, texture_width: u32, texture_height: u32) -> (u32, u32) {
+        match (self.width, self.height) {
+            (Some(width), Some(height)) => (width, height),
+            (Some(width), None) => (width, texture_height),
+            (None, Some(height)) => (texture_width, height),
+            (None, None) => {
+                let size = texture_width.min(texture_height);
+                (size, size)
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
-pub enum AnimationFrames {
-    Index(Vec<i32>),
+pub enum AnimationFrame {
+    Index(u32),
     IndexTime { index: u32, time: u32 },
 }
+
+impl AnimationFrame {
+    pub fn index(&self) -> u32 {
+        match self {
+            Self::Index(index) => *index,
+            Self::IndexTime { index, time: _ } => *index,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserialize_frames() {
+        let meta: TextureMeta = serde_json::from_str(
+            r#"{
+                "animation": {
+                    "frametime": 2,
+                    "frames": [0, 1, { "index": 2, "time": 10 }]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            meta.animation.unwrap().frames.unwrap(),
+            vec![
+                AnimationFrame::Index(0),
+                AnimationFrame::Index(1),
+                AnimationFrame::IndexTime { index: 2, time: 10 },
+            ]
+        );
+    }
+
+    #[test]
+    fn keep_other_sections() {
+        let raw = serde_json::json!({
+            "texture": { "blur": true },
+            "gui": { "scaling": { "type": "nine_slice", "width": 16, "height": 16, "border": 4 } }
+        });
+        let meta = serde_json::from_value::<TextureMeta>(raw.clone()).unwrap();
+
+        assert!(meta.animation.is_none());
+        assert_eq!(serde_json::to_value(&meta).unwrap(), raw);
+    }
+
+    #[test]
+    fn frame_size_fallback() {
+        let animation = TextureAnimation::default();
+        assert_eq!(animation.frame_size(16, 64), (16, 16));
+
+        let animation = TextureAnimation {
+            height: Some(8),
+            ..Default::default()
+        };
+        assert_eq!(animation.frame_size(16, 64), (16, 8));
+    }
+}
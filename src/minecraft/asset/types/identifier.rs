@@ -10,15 +10,18 @@ use serde::{de::Visitor, Deserialize, Serialize};
 
 const DEFAULT_NAMESPACE: &str = "minecraft";
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(rename_all = "snake_case")]
 pub enum AssetType {
     Model,
     Blockstate,
     Texture,
     Atlas,
+    Font,
     TextureMeta,
+    TextureRecipe,
     Sound,
+    SoundDefinitions,
     Particle,
     Text,
     Language,
@@ -27,6 +30,28 @@ pub enum AssetType {
     ItemModelDefinition,
 }
 
+impl AssetType {
+    /// Whether copies of this asset from several bundles are merged rather than overridden.
+    pub fn is_mergeable(&self) -> bool {
+        matches!(self, Self::SoundDefinitions | Self::Language)
+    }
+
+    /// Whether copies of this asset are combined using a [`MergeStrategy`].
+    ///
+    /// [`MergeStrategy`]: crate::asset::merge::MergeStrategy
+    pub fn has_merge_strategy(&self) -> bool {
+        matches!(
+            self,
+            Self::Model
+                | Self::Blockstate
+                | Self::Atlas
+                | Self::Font
+                | Self::TextureMeta
+                | Self::ItemModelDefinition
+        )
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Identifier {
     pub namespace: String,
@@ -107,13 +132,70 @@ impl Identifier {
                     .and_then(|extension| match extension {
                         "png" => Ok(AssetType::Texture),
                         "mcmeta" => Ok(AssetType::TextureMeta),
+                        "toml"
+                            if value.with_extension("").extension() == Some("recipe".as_ref()) =>
+                        {
+                            Ok(AssetType::TextureRecipe)
+                        }
                         _ => Err(anyhow!(
                             "Texture extension '{}' unsupported: {}",
                             extension,
                             value.to_string_lossy()
                         )),
                     }),
+                "sounds" => value
+                    .extension()
+                    .and_then(OsStr::to_str)
+                    .with_context(|| {
+                        format!(
+                            "Failed to parse sound extension: {}",
+                            value.to_string_lossy()
+                        )
+                    })
+                    .and_then(|extension| match extension {
+                        "ogg" => Ok(AssetType::Sound),
+                        _ => Err(anyhow!(
+                            "Sound extension '{}' unsupported: {}",
+                            extension,
+                            value.to_string_lossy()
+                        )),
+                    }),
+                "sounds.json" => Ok(AssetType::SoundDefinitions),
+                "lang" => value
+                    .extension()
+                    .and_then(OsStr::to_str)
+                    .with_context(|| {
+                        format!(
+                            "Failed to parse language extension: {}",
+                            value.to_string_lossy()
+                        )
+                    })
+                    .and_then(|extension| match extension {
+                        "json" => Ok(AssetType::Language),
+                        _ => Err(anyhow!(
+                            "Language extension '{}' unsupported: {}",
+                            extension,
+                            value.to_string_lossy()
+                        )),
+                    }),
                 "atlases" => Ok(AssetType::Atlas),
+                "font" => value
+                    .extension()
+                    .and_then(OsStr::to_str)
+                    .with_context(|| {
+                        format!(
+                            "Failed to parse font extension: {}",
+                            value.to_string_lossy()
+                        )
+                    })
+                    .and_then(|extension| match extension {
+                        "json" => Ok(AssetType::Font),
+                        _ => Err(anyhow!(
+                            "Font extension '{}' unsupported: {}",
+                            extension,
+                            value.to_string_lossy()
+                        )),
+                    }),
                 "modifiers" => Ok(AssetType::Modifier),
                 "modifiers.toml" => Ok(AssetType::ModifierIndex),
                 "items" => Ok(AssetType::ItemModelDefinition),
@@ -124,10 +206,13 @@ impl Identifier {
                 )),
             })?;
 
-        let asset_path = path_list
-            .collect::<PathBuf>()
-            .with_extension("")
-            .with_extension("");
+        let asset_path = match asset_type {
+            AssetType::SoundDefinitions => PathBuf::from("sounds"),
+            _ => path_list
+                .collect::<PathBuf>()
+                .with_extension("")
+                .with_extension(""),
+        };
 
         Ok((asset_type, Identifier::new(namespace, asset_path)))
     }
@@ -138,8 +223,11 @@ impl Identifier {
             AssetType::Blockstate => ("blockstates", "json"),
             AssetType::Texture => ("textures", "png"),
             AssetType::Atlas => ("atlases", "json"),
+            AssetType::Font => ("font", "json"),
             AssetType::TextureMeta => ("textures", "png.mcmeta"),
+            AssetType::TextureRecipe => ("textures", "recipe.toml"),
             AssetType::Sound => ("sounds", "ogg"),
+            AssetType::SoundDefinitions => (".", "json"),
             AssetType::Text => ("texts", "txt"),
             AssetType::Particle => ("particles", "json"),
             AssetType::Language => ("lang", "json"),
@@ -317,6 +405,18 @@ mod tests {
         assert_eq!((AssetType::TextureMeta, id), result);
     }
 
+    #[test]
+    fn from_path_texture_recipe() {
+        let id = Identifier::new("quark", "block/red_glass");
+        let result = Identifier::from_path("quark/textures/block/red_glass.recipe.toml").unwrap();
+        assert_eq!((AssetType::TextureRecipe, id), result);
+    }
+
+    #[test]
+    fn from_path_texture_toml_invalid() {
+        assert!(Identifier::from_path("quark/textures/block/red_glass.toml").is_err());
+    }
+
     #[test]
     fn from_path_minecraft_atlas() {
         let id = Identifier::minecraft("blocks");
@@ -324,6 +424,53 @@ mod tests {
         assert_eq!((AssetType::Atlas, id), result);
     }
 
+    #[test]
+    fn from_path_minecraft_sound() {
+        let id = Identifier::minecraft("ambient/cave/cave1");
+        let result = Identifier::from_path("minecraft/sounds/ambient/cave/cave1.ogg").unwrap();
+        assert_eq!((AssetType::Sound, id), result);
+    }
+
+    #[test]
+    fn from_path_minecraft_sound_invalid() {
+        let result = Identifier::from_path("minecraft/sounds/ambient/cave/cave1.wav");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_path_sound_definitions() {
+        let id = Identifier::new("quark", "sounds");
+        let result = Identifier::from_path("quark/sounds.json").unwrap();
+        assert_eq!((AssetType::SoundDefinitions, id), result);
+    }
+
+    #[test]
+    fn sound_definitions_to_path() {
+        let id = Identifier::new("quark", "sounds");
+        let result = id.to_path(Path::new("assets"), &AssetType::SoundDefinitions);
+        assert_eq!(PathBuf::from("assets/quark/./sounds.json"), result);
+    }
+
+    #[test]
+    fn from_path_language() {
+        let id = Identifier::new("quark", "en_us");
+        let result = Identifier::from_path("quark/lang/en_us.json").unwrap();
+        assert_eq!((AssetType::Language, id), result);
+    }
+
+    #[test]
+    fn from_path_language_legacy() {
+        let result = Identifier::from_path("quark/lang/en_us.lang");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_path_minecraft_font() {
+        let id = Identifier::minecraft("default");
+        let result = Identifier::from_path("minecraft/font/default.json").unwrap();
+        assert_eq!((AssetType::Font, id), result);
+    }
+
     #[test]
     fn from_path_other() {
         let id = Identifier::new("quark", "block/sponge");
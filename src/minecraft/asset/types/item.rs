@@ -9,7 +9,7 @@ use serde_with::{serde_as, skip_serializing_none, OneOrMany};
 
 use super::identifier::{AssetType, Identifier};
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ItemModelDefinition {
     #[serde(default = "get_true", skip_serializing_if = "is_true")]
     pub hand_animation_on_swap: bool,
@@ -28,7 +28,7 @@ impl LoadableAsset for ItemModelDefinition {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ModelType {
     #[serde(alias = "minecraft:model")]
@@ -79,6 +79,46 @@ pub enum ModelType {
 }
 
 impl ModelType {
+    /// Every model nested directly within this one.
+    pub fn children_mut(&mut self) -> Vec<&mut ModelType> {
+        match self {
+            Self::Composite { models } => models.iter_mut().collect(),
+            Self::Condition {
+                property: _,
+                on_true,
+                on_false,
+            } => vec![on_true.as_mut(), on_false.as_mut()],
+            Self::Select { property, fallback } => {
+                let mut children = property.case_models_mut();
+                children.push(fallback.as_mut());
+                children
+            }
+            Self::RangeDispatch {
+                entries, fallback, ..
+            } => entries
+                .iter_mut()
+                .map(|entry| &mut entry.model)
+                .chain([fallback.as_mut()])
+                .collect(),
+            Self::Model { .. } | Self::Empty | Self::BundleSelectedItem | Self::Special { .. } => {
+                Vec::new()
+            }
+        }
+    }
+
+    /// Every model identifier within the tree, including the base of special models.
+    pub fn models_mut(&mut self) -> Vec<&mut Identifier> {
+        match self {
+            Self::Model { model, .. } => vec![model],
+            Self::Special { base, .. } => vec![base],
+            _ => self
+                .children_mut()
+                .into_iter()
+                .flat_map(ModelType::models_mut)
+                .collect(),
+        }
+    }
+
     fn get_default_range_dispatch_scale() -> f32 {
         1.0
     }
@@ -88,7 +128,7 @@ impl ModelType {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum TintSource {
     #[serde(alias = "minecraft:constant")]
@@ -113,19 +153,19 @@ pub enum TintSource {
     },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DefaultColor {
     default: ColorRGB,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ColorRGB {
     Packed(i32),
     Array([f32; 3]),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "property", rename_all = "snake_case")]
 pub enum ConditionProperty {
     #[serde(alias = "minecraft:using_item")]
@@ -162,7 +202,7 @@ pub enum ConditionProperty {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "property", rename_all = "snake_case")]
 pub enum SelectProperty {
     #[serde(alias = "minecraft:main_hand")]
@@ -200,14 +240,30 @@ pub enum SelectProperty {
     },
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl SelectProperty {
+    pub fn case_models_mut(&mut self) -> Vec<&mut ModelType> {
+        match self {
+            Self::MainHand { cases } => SelectCase::models_mut(cases),
+            Self::ChargeType { cases } => SelectCase::models_mut(cases),
+            Self::TrimMaterial { cases } => SelectCase::models_mut(cases),
+            Self::BlockState { cases, .. } => SelectCase::models_mut(cases),
+            Self::DisplayContext { cases } => SelectCase::models_mut(cases),
+            Self::LocalTime { cases, .. } => SelectCase::models_mut(cases),
+            Self::ContextDimension { cases } => SelectCase::models_mut(cases),
+            Self::ContextEntityType { cases } => SelectCase::models_mut(cases),
+            Self::CustomModelData { cases, .. } => SelectCase::models_mut(cases),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum MainHand {
     Left,
     Right,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum ChargeType {
     None,
@@ -215,7 +271,7 @@ pub enum ChargeType {
     Arrow,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DisplayContext {
     None,
@@ -229,7 +285,7 @@ pub enum DisplayContext {
     Fixed,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CustomModelData {
     #[serde(default, skip_serializing_if = "CustomModelData::is_default")]
     index: i32,
@@ -242,20 +298,26 @@ impl CustomModelData {
 }
 
 #[serde_as]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SelectCase<T: Serialize + DeserializeOwned> {
     #[serde_as(as = "OneOrMany<_>")]
-    when: Vec<T>,
-    model: ModelType,
+    pub when: Vec<T>,
+    pub model: ModelType,
+}
+
+impl<T: Serialize + DeserializeOwned> SelectCase<T> {
+    fn models_mut(cases: &mut [Self]) -> Vec<&mut ModelType> {
+        cases.iter_mut().map(|case| &mut case.model).collect()
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RangeDispatchEntry {
-    threshold: f32,
-    model: ModelType,
+    pub threshold: f32,
+    pub model: ModelType,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "property", rename_all = "snake_case")]
 pub enum NumericProperty {
     #[serde(rename = "bundle/fullness", alias = "minecraft:bundle/fullness")]
@@ -313,7 +375,7 @@ impl NumericProperty {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TimeSource {
     Daytime,
@@ -321,7 +383,7 @@ pub enum TimeSource {
     Random,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CompassTarget {
     Spawn,
@@ -331,7 +393,7 @@ pub enum CompassTarget {
 }
 
 #[skip_serializing_none]
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SpecialModel {
     #[serde(alias = "minecraft:bed")]
@@ -397,7 +459,7 @@ impl SpecialModel {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum DyeColor {
     White,
@@ -418,7 +480,7 @@ pub enum DyeColor {
     Black,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum HeadKind {
     Skeleton,
@@ -430,13 +492,13 @@ pub enum HeadKind {
     Dragon,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SignModel {
     wood_type: SignWoodType,
     texture: Identifier,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum SignWoodType {
     Oak,
@@ -1,3 +1,5 @@
+use std::ops::Add;
+
 use anyhow::anyhow;
 use serde::{de::Visitor, Deserialize, Serialize};
 
@@ -23,6 +25,23 @@ impl StateRotation {
             Self::Degrees270 => 3,
         }
     }
+
+    pub fn from_quarters(quarters: u8) -> Self {
+        match quarters % 4 {
+            0 => Self::Degrees0,
+            1 => Self::Degrees90,
+            2 => Self::Degrees180,
+            _ => Self::Degrees270,
+        }
+    }
+}
+
+impl Add for StateRotation {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::from_quarters(self.quarters() + rhs.quarters())
+    }
 }
 
 impl<'de> Deserialize<'de> for StateRotation {
@@ -83,6 +102,16 @@ impl Visitor<'_> for StateRotationVisitor {
     {
         StateRotation::try_from(v).map_err(|e| E::custom(e))
     }
+
+    // TOML only has signed integers.
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        u64::try_from(v)
+            .map_err(|e| E::custom(e))
+            .and_then(|v| self.visit_u64(v))
+    }
 }
 
 #[cfg(test)]
@@ -130,4 +159,23 @@ mod tests {
     fn number_to_rotation_unaligned() {
         assert!(StateRotation::try_from(1).is_err())
     }
+
+    #[test]
+    fn rotation_from_toml() {
+        #[derive(Deserialize)]
+        struct Rotations {
+            rotations: Vec<StateRotation>,
+        }
+
+        let parsed = toml::from_str::<Rotations>("rotations = [0, 90, 450]").unwrap();
+
+        assert_eq!(
+            parsed.rotations,
+            vec![
+                StateRotation::Degrees0,
+                StateRotation::Degrees90,
+                StateRotation::Degrees90
+            ]
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use serde::Serialize;
+use serde::{Serialize, Serializer};
 
 #[derive(Debug, Clone, Serialize, PartialEq)]
 #[serde(untagged)]
@@ -48,6 +48,10 @@ impl From<FloatInt> for f32 {
     }
 }
 
+pub fn serialize_float_int<S: Serializer>(value: &f32, serializer: S) -> Result<S::Ok, S::Error> {
+    FloatInt::from(*value).serialize(serializer)
+}
+
 #[inline]
 pub fn is_true(value: &bool) -> bool {
     *value
@@ -10,11 +10,13 @@ use std::{
 use anyhow::Context;
 use indexmap::IndexSet;
 use notify::{INotifyWatcher, RecursiveMode, Watcher};
-use tokio::task::JoinSet;
+use tokio::{sync::Mutex, task::JoinSet};
 
 use crate::{
     changelog::Changelog,
-    compile::{deploy::DeployAPIContext, tracking::AssetTracker, PackCompiler},
+    compile::{
+        deploy::DeployAPIContext, tracking::AssetTracker, vanilla::VanillaAssets, PackCompiler,
+    },
     config::PackConfig,
     sanitize::PathSanitizer,
 };
@@ -29,6 +31,8 @@ pub struct Runner {
     profile: String,
     api_context: Option<DeployAPIContext>,
     changed: Arc<AtomicBool>,
+    /// Vanilla assets of the last build with their version, reused while the version is the same.
+    vanilla: Mutex<Option<(String, Arc<VanillaAssets>)>>,
 }
 
 impl Runner {
@@ -50,6 +54,7 @@ impl Runner {
             profile,
             api_context: None,
             changed: Arc::new(AtomicBool::new(true)),
+            vanilla: Mutex::default(),
         })
     }
 
@@ -78,11 +83,12 @@ impl Runner {
                 version_number,
             )?),
             changed: Arc::new(AtomicBool::new(true)),
+            vanilla: Mutex::default(),
         })
     }
 
     pub async fn run(&'static self) -> anyhow::Result<()> {
-        let compilers = self.create_compilers()?;
+        let compilers = self.create_compilers().await?;
 
         if let Some(api_context) = &self.api_context {
             let changelog = Changelog::new()?.collect_changelog().await?;
@@ -156,7 +162,21 @@ impl Runner {
         std::future::pending().await
     }
 
-    fn create_compilers(&self) -> anyhow::Result<Vec<PackCompiler>> {
+    async fn load_vanilla(&self, version: &str) -> anyhow::Result<Arc<VanillaAssets>> {
+        let mut cached = self.vanilla.lock().await;
+
+        if let Some((cached_version, vanilla)) = cached.as_ref() {
+            if cached_version == version {
+                return Ok(vanilla.clone());
+            }
+        }
+
+        let vanilla = Arc::new(VanillaAssets::load(&self.minecraft_path, version).await?);
+        *cached = Some((version.to_string(), vanilla.clone()));
+        Ok(vanilla)
+    }
+
+    async fn create_compilers(&self) -> anyhow::Result<Vec<PackCompiler<'_>>> {
         let config_raw = std::fs::read_to_string(&self.config).context("Config read error.")?;
         let config = toml::from_str::<PackConfig>(&config_raw).context("Config parse error.")?;
 
@@ -166,6 +186,11 @@ impl Runner {
 
         let asset_tracker = Arc::from(asset_tracker);
 
+        let vanilla = match &config.vanilla {
+            Some(version) => Some(self.load_vanilla(version).await?),
+            None => None,
+        };
+
         let mut compilers = Vec::with_capacity(self.builds.len());
         let profile = config.get_profile(&self.profile)?;
         let builds = self.filter_build_list(&config);
@@ -181,6 +206,7 @@ impl Runner {
                 profile.clone(),
                 build,
                 asset_tracker.clone(),
+                vanilla.clone(),
             )?;
             compilers.push(compiler);
         }